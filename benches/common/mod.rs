@@ -0,0 +1,37 @@
+//! Helpers shared by the benchmarks.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+pub const RUNS: usize = 10;
+
+/// Deterministic xorshift generator, so that every run sees the same inputs.
+pub struct Rng(pub u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+/// Runs `run` [`RUNS`] times and returns the best and median times taken.
+pub fn time<T>(mut run: impl FnMut() -> T) -> (Duration, Duration) {
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(run());
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    (times[0], times[RUNS / 2])
+}
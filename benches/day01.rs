@@ -0,0 +1,101 @@
+//! Times decoding calibration documents with a digit-word automaton built for every line, as
+//! the solver of part 2 used to, against one built once and reused.
+//!
+//! Run with `cargo bench --bench day01`.
+
+use std::{collections::HashMap, hint::black_box};
+
+use aho_corasick::AhoCorasick;
+use aoc_2023::day01::{CalibrationDecoder, Policy};
+
+mod common;
+
+use common::{time, Rng, RUNS};
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Lines of the length of the puzzle's that mix letters, numerals and digit words, with at
+/// least one numeral each.
+fn document(rng: &mut Rng, lines: usize) -> String {
+    (0..lines)
+        .map(|_| {
+            let mut line: String = (0..rng.below(6) + 2)
+                .map(|_| match rng.below(3) {
+                    0 => WORDS[rng.below(9)].to_string(),
+                    1 => char::from(b'1' + rng.below(9) as u8).to_string(),
+                    _ => char::from(b'a' + rng.below(26) as u8).to_string(),
+                })
+                .collect();
+            line.push(char::from(b'1' + rng.below(9) as u8));
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+/// The calibration value of `line` as part 2 used to compute it, building the table of digit
+/// words and its automaton anew for every line.
+fn rebuilt_per_line(line: &str) -> u32 {
+    let words = HashMap::<&str, u32>::from([
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("four", 4),
+        ("five", 5),
+        ("six", 6),
+        ("seven", 7),
+        ("eight", 8),
+        ("nine", 9),
+        ("0", 0),
+        ("1", 1),
+        ("2", 2),
+        ("3", 3),
+        ("4", 4),
+        ("5", 5),
+        ("6", 6),
+        ("7", 7),
+        ("8", 8),
+        ("9", 9),
+    ]);
+    let keys: Vec<_> = words.keys().copied().collect();
+    let ac = AhoCorasick::new(&keys).unwrap();
+    let mut digits = ac.find_iter(line);
+    let first_digit = words[keys[digits.next().unwrap().pattern()]];
+    let last_digit = digits
+        .last()
+        .map_or(first_digit, |m| words[keys[m.pattern()]]);
+    first_digit * 10 + last_digit
+}
+
+fn main() {
+    let mut rng = Rng(0x2023_1201);
+    let puzzle = document(&mut rng, 1000);
+    println!("best and median of {RUNS} runs");
+    let (best, median) = time(|| {
+        puzzle
+            .lines()
+            .map(|line| u64::from(rebuilt_per_line(line)))
+            .sum::<u64>()
+    });
+    println!("1000 lines, rebuilt per line: {best:>9.2?} {median:>9.2?}");
+    let (best, median) = time(|| {
+        let decoder = CalibrationDecoder::english();
+        decoder
+            .sum_lines(black_box(puzzle.as_bytes()), Policy::Fail)
+            .unwrap()
+    });
+    println!("1000 lines, built once:       {best:>9.2?} {median:>9.2?}");
+    let large = document(&mut rng, 1_000_000);
+    let decoder = CalibrationDecoder::english();
+    let (best, median) = time(|| {
+        decoder
+            .sum_lines(black_box(large.as_bytes()), Policy::Fail)
+            .unwrap()
+    });
+    println!(
+        "{} MB streamed, built once:  {best:>9.2?} {median:>9.2?}",
+        large.len() / 1_000_000
+    );
+}
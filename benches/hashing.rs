@@ -0,0 +1,100 @@
+//! Times the solvers whose inner loops are dominated by hashing, on generated inputs of the
+//! size of the real puzzles.
+//!
+//! Compare `cargo bench --bench hashing` with `cargo bench --bench hashing --features fast-hash`.
+
+use std::hint::black_box;
+
+use aoc_2023::runner;
+
+mod common;
+
+use common::{time, Rng, RUNS};
+
+fn grid(size: usize, mut tile: impl FnMut(usize, usize) -> char) -> String {
+    (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|col| tile(row, col))
+                .chain(['\n'])
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// Condition records whose damaged groups come from filling in the unknown springs at random.
+fn day12(rng: &mut Rng) -> String {
+    (0..1000)
+        .map(|_| {
+            let springs: Vec<bool> = (0..20).map(|_| rng.below(3) == 0).collect();
+            let conditions: String = springs
+                .iter()
+                .map(|&damaged| match (damaged, rng.below(2) == 0) {
+                    (_, true) => '?',
+                    (true, false) => '#',
+                    (false, false) => '.',
+                })
+                .collect();
+            let groups: Vec<String> = springs
+                .split(|&damaged| !damaged)
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len().to_string())
+                .collect();
+            format!("{conditions} {}\n", groups.join(","))
+        })
+        .filter(|line| !line.ends_with(" \n"))
+        .collect()
+}
+
+fn day16(rng: &mut Rng) -> String {
+    grid(110, |_, _| match rng.below(40) {
+        0 => '/',
+        1 => '\\',
+        2 => '|',
+        3 => '-',
+        _ => '.',
+    })
+}
+
+fn day17(rng: &mut Rng) -> String {
+    grid(141, |_, _| char::from(b'1' + rng.below(9) as u8))
+}
+
+/// A garden whose rocks never touch, so that the shortest paths are the Manhattan ones that
+/// the solver of part 2 relies on.
+fn day21(rng: &mut Rng) -> String {
+    grid(131, |row, col| match (row, col) {
+        (65, 65) => 'S',
+        (row, col)
+            if row % 2 == 1 && col % 2 == 1 && row != 65 && col != 65 && rng.below(4) == 0 =>
+        {
+            '#'
+        }
+        _ => '.',
+    })
+}
+
+fn main() {
+    let mut rng = Rng(0x2023_1225);
+    let inputs = [
+        (12, day12(&mut rng)),
+        (16, day16(&mut rng)),
+        (17, day17(&mut rng)),
+        (21, day21(&mut rng)),
+    ];
+    let hasher = if cfg!(feature = "fast-hash") {
+        "FxHash"
+    } else {
+        "SipHash"
+    };
+    println!("hasher: {hasher}, best and median of {RUNS} runs");
+    for (day, input) in inputs {
+        let solvers = runner::solvers(day).unwrap();
+        let (best, median) = time(|| {
+            for &solver in solvers {
+                runner::solve(solver, black_box(&input)).unwrap();
+            }
+        });
+        println!("day {day:02}: {best:>9.2?} {median:>9.2?}");
+    }
+}
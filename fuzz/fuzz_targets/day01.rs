@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc_2023::day01::{parse_input, solve_part1, solve_part2, Policy};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(data) = parse_input(input) {
+        let _ = solve_part1(&data, Policy::Fail);
+        let _ = solve_part2(&data, Policy::Skip);
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc_2023::day02::{parse_input, puzzle_bag, solve_part1, solve_part2};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(games) = parse_input(input) {
+        solve_part1(&games, &puzzle_bag());
+        solve_part2(&games);
+    }
+});
@@ -0,0 +1,27 @@
+#![no_main]
+
+use std::num::NonZeroUsize;
+
+use aoc_2023::day04::{
+    fibonacci, linear, parse_input, report_with, score, solve_part1, solve_part2, solve_part2_with,
+    CopyRule,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(data) = parse_input(input) {
+        solve_part1(&data);
+        let answer2 = solve_part2(&data);
+        assert_eq!(solve_part2_with(&data, &CopyRule::default()), answer2);
+        let rule = CopyRule {
+            offset: NonZeroUsize::new(2).unwrap(),
+            cap: NonZeroUsize::new(100),
+        };
+        score(&data, linear);
+        solve_part2_with(&data, &rule);
+        report_with(&data, fibonacci, &rule);
+    }
+});
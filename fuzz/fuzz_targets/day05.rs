@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc_2023::day05::{into_intervals, parse_input, solve_part1, solve_part2};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok((seeds, mappings)) = parse_input(input) {
+        solve_part1(&seeds, &mappings);
+        if let Some(seeds) = into_intervals(&seeds) {
+            solve_part2(seeds, &mappings);
+        }
+    }
+});
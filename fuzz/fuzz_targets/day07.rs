@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc_2023::day07::{parse_input, solve_part1, solve_part2};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(data) = parse_input(input) {
+        solve_part1(&data);
+        solve_part2(&data);
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc_2023::day09::{parse_input, solve};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(values) = parse_input(input) {
+        solve(&values);
+    }
+});
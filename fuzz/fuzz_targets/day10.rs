@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc_2023::day10::{find_circuit, parse_input, solve_part1, solve_part2};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok((grid, start)) = parse_input(input) {
+        if let Some(circuit) = find_circuit(&grid, start) {
+            solve_part1(&circuit);
+            solve_part2(&grid, &circuit);
+        }
+    }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc_2023::day12::{parse_input, solve, Memoization};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(records) = parse_input(input) {
+        let mut memo = Memoization::default();
+        solve(&records, &mut memo);
+        let records: Vec<_> = records.into_iter().map(|r| r.unfold()).collect();
+        solve(&records, &mut memo);
+    }
+});
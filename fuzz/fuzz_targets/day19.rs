@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc_2023::day19::{parse_input, solve_part1, solve_part2};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok((workflows, parts)) = parse_input(input) {
+        solve_part1(&workflows, &parts);
+        solve_part2(&workflows);
+    }
+});
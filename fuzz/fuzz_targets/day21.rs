@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc_2023::day21::{parse_input, solve_part1, solve_part2};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok((garden, start)) = parse_input(input) {
+        solve_part1(&garden, start);
+        solve_part2(&garden, start);
+    }
+});
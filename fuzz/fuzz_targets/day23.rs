@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc_2023::day23::{parse_input, solve_part1, solve_part2};
+use aoc_2023_fuzz::budget;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let mut budget = budget();
+    if let Ok(data) = parse_input(input) {
+        let _ = solve_part1(&data, &mut budget);
+        let _ = solve_part2(&data, &mut budget);
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc_2023::day24::{parse_input, solve_part1, solve_part2};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(hailstones) = parse_input(input) {
+        solve_part1::<7, 27>(&hailstones);
+        solve_part2(&hailstones);
+    }
+});
@@ -0,0 +1,12 @@
+//! Helpers shared by the fuzz targets.
+
+use aoc_2023::budget::Budget;
+
+/// Steps granted to the solvers that may run for very long: inputs whose answers lie further
+/// than this are not worth the fuzzer's time.
+pub const STEPS: u64 = 1 << 20;
+
+/// A budget of [`STEPS`] steps.
+pub fn budget() -> Budget {
+    Budget::unlimited().with_steps(STEPS)
+}
@@ -0,0 +1,117 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use serde_json::Value;
+
+/// The answer to a part of a puzzle.
+///
+/// Non-negative integers are always held as `Unsigned`, so that answers compare equal whatever
+/// the integer type the solver computed them with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Unsigned(n as u64)
+            }
+        })*
+    };
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                match u64::try_from(n) {
+                    Ok(n) => Answer::Unsigned(n),
+                    Err(_) => Answer::Signed(n as i64),
+                }
+            }
+        })*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, usize);
+from_signed!(i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => n.fmt(f),
+            Answer::Signed(n) => n.fmt(f),
+            Answer::Text(text) => text.fmt(f),
+        }
+    }
+}
+
+/// Reads back an answer as displayed: integers become numbers and anything else text.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match (s.parse::<u64>(), s.parse::<i64>()) {
+            (Ok(n), _) => Answer::Unsigned(n),
+            (_, Ok(n)) => Answer::Signed(n),
+            _ => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+/// Integers become JSON numbers and text a JSON string.
+impl From<&Answer> for Value {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Unsigned(n) => Value::from(*n),
+            Answer::Signed(n) => Value::from(*n),
+            Answer::Text(text) => Value::from(text.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(42u16), Answer::from(42i64));
+        assert_eq!(Answer::from(42usize), Answer::Unsigned(42));
+        assert_eq!(Answer::from(-3i64), Answer::Signed(-3));
+        assert_eq!(Answer::from("LR"), Answer::Text("LR".to_string()));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for answer in [
+            Answer::Unsigned(u64::MAX),
+            Answer::Signed(-3),
+            Answer::Text("EOF".to_string()),
+        ] {
+            assert_eq!(answer.to_string().parse(), Ok(answer));
+        }
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(Value::from(&Answer::Unsigned(283)).to_string(), "283");
+        assert_eq!(Value::from(&Answer::Signed(-3)).to_string(), "-3");
+        assert_eq!(Value::from(&Answer::from("LR")).to_string(), "\"LR\"");
+    }
+}
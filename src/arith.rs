@@ -0,0 +1,64 @@
+//! Overflow checks for the arithmetic on puzzle-sized numbers.
+//!
+//! `checked!(a + b)`, `checked!(a - b)` and `checked!(a * b)` evaluate like the bare expression.
+//! With the `checked-arithmetic` feature they panic on overflow, even in release builds, naming
+//! the module and the expression that overflowed. Each operand is a path such as
+//! `entry.target` or a parenthesised expression.
+
+macro_rules! checked {
+    (@ $method:ident, $op:tt, $lhs:tt, $rhs:tt, $expression:expr) => {{
+        #[cfg(feature = "checked-arithmetic")]
+        let value = $crate::arith::check(
+            $lhs.$method($rhs),
+            concat!(module_path!(), ": ", $expression),
+        );
+        #[cfg(not(feature = "checked-arithmetic"))]
+        let value = $lhs $op $rhs;
+        value
+    }};
+    ($($lhs:tt).+ + $($rhs:tt).+) => {
+        checked!(@ checked_add, +, ($($lhs).+), ($($rhs).+), stringify!($($lhs).+ + $($rhs).+))
+    };
+    ($($lhs:tt).+ - $($rhs:tt).+) => {
+        checked!(@ checked_sub, -, ($($lhs).+), ($($rhs).+), stringify!($($lhs).+ - $($rhs).+))
+    };
+    ($($lhs:tt).+ * $($rhs:tt).+) => {
+        checked!(@ checked_mul, *, ($($lhs).+), ($($rhs).+), stringify!($($lhs).+ * $($rhs).+))
+    };
+}
+
+#[cfg(feature = "checked-arithmetic")]
+#[track_caller]
+pub(crate) fn check<T>(value: Option<T>, expression: &str) -> T {
+    match value {
+        Some(value) => value,
+        None => panic!("arithmetic overflow in {expression}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    struct Entry {
+        target: u64,
+    }
+
+    #[test]
+    fn test_checked() {
+        let entry = Entry { target: 7 };
+        let source = 5u64;
+        assert_eq!(checked!(entry.target + (source - 2)), 10);
+        assert_eq!(checked!(entry.target - source), 2);
+        assert_eq!(checked!((entry.target * 2) * source), 70);
+    }
+
+    #[test]
+    #[cfg(feature = "checked-arithmetic")]
+    #[should_panic(
+        expected = "arithmetic overflow in aoc_2023::arith::test: entry.target * factor"
+    )]
+    fn test_checked_overflow() {
+        let entry = Entry { target: u64::MAX };
+        let factor = 2;
+        checked!(entry.target * factor);
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    fs, io,
+    net::{Ipv4Addr, TcpListener},
+    path::{Path, PathBuf},
+    process::{self, ExitCode},
+    time::Duration,
+};
+
+use clap::{Parser, Subcommand};
+
+#[cfg(target_os = "linux")]
+use aoc_2023::watch;
+use aoc_2023::{budget::Budget, identify, repl, report, runner, scaffold, serve, stats};
+
+#[derive(Parser)]
+#[command(about = "Tooling around the Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve both parts of a day
+    Run {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Input file, instead of data/dayNN.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Give up on the solvers that support it after this many seconds per part
+        #[arg(long, value_parser = seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Find the days whose puzzle input format a file follows
+    Identify { file: PathBuf },
+    /// Query the parsed input of a day interactively
+    Repl {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Input file, instead of data/dayNN.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Solve every day's input and write the answers, timings and drawings to one HTML file
+    Report {
+        #[arg(long, default_value = "report.html")]
+        output: PathBuf,
+        /// Give up on the solvers that support it after this many seconds per day
+        #[arg(long, value_parser = seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Answer `POST /day/<day>/part/<part>` requests on localhost
+    Serve {
+        #[arg(long, default_value_t = 8023)]
+        port: u16,
+        /// Give up on the solvers that support it after this many seconds
+        #[arg(long, value_parser = seconds, default_value = "10")]
+        timeout: Duration,
+    },
+    /// Summarise the structure of the input of a day
+    Stats {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Input file, instead of data/dayNN.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Solve the input and example of a day again whenever they change
+    #[cfg(target_os = "linux")]
+    Watch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Input file, instead of data/dayNN.txt
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Give up on the solvers that support it after this many seconds per part
+        #[arg(long, value_parser = seconds, default_value = "10")]
+        timeout: Duration,
+    },
+    /// Generate the skeleton of a new day and register it with the runner
+    New {
+        /// The year of the puzzle, which must be the one this crate solves
+        #[arg(value_parser = clap::value_parser!(u16).range(2015..))]
+        year: u16,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+fn input_path(day: u8) -> PathBuf {
+    Path::new("data").join(format!("day{day:02}.txt"))
+}
+
+fn example_path(day: u8) -> PathBuf {
+    Path::new("data/examples").join(format!("day{day:02}.txt"))
+}
+
+fn read_input(day: u8, input: Option<PathBuf>) -> Result<String, String> {
+    let path = input.unwrap_or_else(|| input_path(day));
+    fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// The abbreviated hash of the checked-out commit, if the crate is in a git work tree.
+fn commit() -> Option<String> {
+    let output = process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn seconds(arg: &str) -> Result<Duration, String> {
+    let seconds = arg
+        .parse()
+        .map_err(|e: std::num::ParseFloatError| e.to_string())?;
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
+}
+
+fn budget(timeout: Option<Duration>) -> Budget {
+    match timeout {
+        Some(timeout) => Budget::unlimited().with_time(timeout),
+        None => Budget::unlimited(),
+    }
+}
+
+fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Run {
+            day,
+            input,
+            timeout,
+        } => {
+            let solvers = runner::solvers(day).ok_or(format!("day {day} is not solved yet"))?;
+            let input = read_input(day, input)?;
+            let mut failed = false;
+            for (part, solver) in (1..).zip(solvers) {
+                match runner::solve_within(*solver, &input, budget(timeout)) {
+                    Ok(answer) => println!("The answer to part {part} is {answer}"),
+                    Err(e) => {
+                        eprintln!("part {part}: {e}");
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                return Err(format!("day {day} is not fully solved"));
+            }
+        }
+        Command::Identify { file } => {
+            let input =
+                fs::read_to_string(&file).map_err(|e| format!("{}: {e}", file.display()))?;
+            let candidates = identify::identify(&input);
+            if !candidates.iter().any(|c| c.parsed) {
+                println!("no day parses {}, the closest are:", file.display());
+            }
+            for candidate in candidates.iter().filter(|c| c.consumed > 0).take(5) {
+                println!(
+                    "day {:02}  {:5.1}%  {}",
+                    candidate.day,
+                    100.0 * candidate.consumed as f64 / input.len().max(1) as f64,
+                    if candidate.parsed {
+                        "parses".to_string()
+                    } else {
+                        format!("invalid input at byte {}", candidate.consumed)
+                    }
+                );
+            }
+        }
+        Command::Repl { day, input } => {
+            let input = read_input(day, input)?;
+            let explorer = repl::explorer(day, &input).map_err(|e| e.to_string())?;
+            repl::run(&*explorer, io::stdin().lock(), io::stdout()).map_err(|e| e.to_string())?;
+        }
+        Command::Report { output, timeout } => {
+            let entries: Vec<_> = runner::SOLVERS
+                .iter()
+                .map(|&(day, solvers)| match read_input(day, None) {
+                    Ok(input) => report::entry(day, solvers, &input, budget(timeout)),
+                    Err(e) => report::Entry {
+                        day,
+                        answers: vec![Err(e)],
+                        elapsed: Default::default(),
+                        checks: Vec::new(),
+                        rendering: None,
+                    },
+                })
+                .collect();
+            let title = match commit() {
+                Some(commit) => format!("Advent of Code 2023 at {commit}"),
+                None => "Advent of Code 2023".to_string(),
+            };
+            fs::write(&output, report::html(&title, &entries))
+                .map_err(|e| format!("{}: {e}", output.display()))?;
+            println!("wrote {}", output.display());
+        }
+        Command::Serve { port, timeout } => {
+            let listener =
+                TcpListener::bind((Ipv4Addr::LOCALHOST, port)).map_err(|e| e.to_string())?;
+            println!("listening on http://{}", listener.local_addr().unwrap());
+            serve::serve(listener, timeout).map_err(|e| e.to_string())?;
+        }
+        Command::Stats { day, input } => {
+            let stats = stats::stats(day, &read_input(day, input)?).map_err(|e| e.to_string())?;
+            let width = stats
+                .iter()
+                .map(|(name, _)| name.len())
+                .max()
+                .unwrap_or_default();
+            for (name, value) in stats {
+                println!("{:<width$}  {value}", format!("{name}:"), width = width + 1);
+            }
+        }
+        #[cfg(target_os = "linux")]
+        Command::Watch {
+            day,
+            input,
+            timeout,
+        } => {
+            let solvers = runner::solvers(day).ok_or(format!("day {day} is not solved yet"))?;
+            let files: Vec<_> = [input.unwrap_or_else(|| input_path(day)), example_path(day)]
+                .into_iter()
+                .filter(|path| path.exists())
+                .collect();
+            if files.is_empty() {
+                return Err(format!("day {day} has neither an input nor an example"));
+            }
+            let budget = budget(Some(timeout));
+            watch::watch(solvers, &budget, &files, io::stdout()).map_err(|e| e.to_string())?;
+        }
+        Command::New { year, day } => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+            let krate = env!("CARGO_PKG_NAME").replace('-', "_");
+            let files = scaffold::scaffold(root, &krate, year, day).map_err(|e| e.to_string())?;
+            for file in files {
+                println!("created {}", file.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
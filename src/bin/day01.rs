@@ -1,67 +1,69 @@
-use aho_corasick::AhoCorasick;
-use std::collections::HashMap;
+use std::{env, fs, process};
 
-fn parse(input: &str) -> Vec<&str> {
-    input.split_terminator('\n').collect()
-}
-
-fn calibration_value_part1(string: &str) -> u32 {
-    let mut digits = string.chars().filter_map(|c| c.to_digit(10));
-    let first_digit = digits.next().unwrap();
-    let last_digit = digits.last().unwrap_or(first_digit);
-    first_digit * 10 + last_digit
-}
-
-fn solve_part1(data: &[&str]) -> u32 {
-    data.iter().map(|s| calibration_value_part1(s)).sum()
-}
+use aoc_2023::day01::{
+    parse_input, solve_part1, solve_part2_with, CalibrationDecoder, CalibrationError, Policy,
+    Vocabulary,
+};
 
-fn calibration_value_part2(string: &str) -> u32 {
-    let words = HashMap::<&str, u32>::from([
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9),
-        ("0", 0),
-        ("1", 1),
-        ("2", 2),
-        ("3", 3),
-        ("4", 4),
-        ("5", 5),
-        ("6", 6),
-        ("7", 7),
-        ("8", 8),
-        ("9", 9),
-    ]);
-    let keys: Vec<_> = words.keys().copied().collect();
-    let ac = AhoCorasick::new(&keys).unwrap();
-    let mut digits = ac.find_iter(string);
-    let first_digit = words
-        .get(keys[digits.next().map(|m| m.pattern()).unwrap()])
-        .unwrap();
-    let last_digit = digits
-        .last()
-        .map(|m| m.pattern())
-        .map(|id| keys[id])
-        .map(|k| words.get(k).unwrap())
-        .unwrap_or(first_digit);
-    first_digit * 10 + last_digit
+/// Reads the digit words from the `word=value` file given as argument, or English ones,
+/// rejecting words that prefix others if `strict`.
+fn decoder(path: Option<String>, strict: bool) -> CalibrationDecoder {
+    match path {
+        Some(path) => {
+            let text = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+            let vocabulary = if strict {
+                Vocabulary::parse_strict(&text)
+            } else {
+                text.parse()
+            };
+            CalibrationDecoder::new(&vocabulary.unwrap_or_else(|e| panic!("{path}: {e}")))
+        }
+        None => CalibrationDecoder::english(),
+    }
 }
 
-fn solve_part2(data: &[&str]) -> u32 {
-    data.iter().map(|s| calibration_value_part2(s)).sum()
+/// Prints how `decoder` read the line at fault before exiting, if there is one.
+fn answer(
+    data: &[&str],
+    decoder: &CalibrationDecoder,
+    answer: Result<u64, CalibrationError>,
+) -> u64 {
+    answer.unwrap_or_else(|e| {
+        if let CalibrationError::NoDigit { line } = e {
+            eprintln!("{}", decoder.report(line, data[line - 1]));
+        }
+        eprintln!("{e}, run with --skip to leave such lines out");
+        process::exit(1)
+    })
 }
 
+/// Takes an optional vocabulary file, `--skip`, which leaves lines without a digit out of the
+/// sums instead of failing, and `--strict`, which rejects vocabulary words that prefix others.
 fn main() {
+    let (flags, paths): (Vec<_>, Vec<_>) =
+        env::args().skip(1).partition(|arg| arg.starts_with("--"));
+    if let Some(flag) = flags
+        .iter()
+        .find(|flag| !["--skip", "--strict"].contains(&flag.as_str()))
+    {
+        eprintln!("unknown option {flag}\nusage: day01 [--skip] [--strict] [words.txt]");
+        process::exit(2)
+    }
+    let policy = if !flags.iter().any(|flag| flag == "--skip") {
+        Policy::Fail
+    } else {
+        Policy::Skip
+    };
     let input = include_str!("../../data/day01.txt");
-    let data = parse(input);
-    let answer1 = solve_part1(&data);
+    let data = parse_input(input).unwrap();
+    let answer1 = answer(
+        &data,
+        &CalibrationDecoder::numerals(),
+        solve_part1(&data, policy),
+    );
     println!("The answer to part 1 is {}", answer1);
-    let answer2 = solve_part2(&data);
+    let strict = flags.iter().any(|flag| flag == "--strict");
+    let decoder = decoder(paths.into_iter().next(), strict);
+    let answer2 = answer(&data, &decoder, solve_part2_with(&data, &decoder, policy));
     println!("The answer to part 2 is {}", answer2);
 }
@@ -1,110 +1,106 @@
-use std::collections::HashMap;
+use std::{env, process};
 
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{char, one_of},
-    combinator::{map, map_res, recognize, value},
-    multi::{many0, many1, separated_list1},
-    sequence::{preceded, separated_pair, terminated},
-    IResult,
+use aoc_2023::day02::{
+    best_extra, parse_bag, parse_input, puzzle_bag, smallest_bag, solve_part1, solve_part2,
+    violations, Cubes,
 };
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-enum Color {
-    Blue,
-    Green,
-    Red,
-}
-
-fn color(input: &str) -> IResult<&str, Color> {
-    alt((
-        value(Color::Blue, tag("blue")),
-        value(Color::Green, tag("green")),
-        value(Color::Red, tag("red")),
-    ))(input)
-}
-
-fn integer(input: &str) -> IResult<&str, u32> {
-    map_res(
-        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
-        |s: &str| s.parse(),
-    )(input)
-}
-
-fn color_count(input: &str) -> IResult<&str, (Color, u32)> {
-    map(separated_pair(integer, tag(" "), color), |(a, b)| (b, a))(input)
-}
-
-fn draw(input: &str) -> IResult<&str, HashMap<Color, u32>> {
-    map(separated_list1(tag(", "), color_count), |v| {
-        v.into_iter().collect()
-    })(input)
-}
-
-fn game_id(input: &str) -> IResult<&str, u32> {
-    preceded(tag("Game "), integer)(input)
-}
-
-fn game(input: &str) -> IResult<&str, Vec<HashMap<Color, u32>>> {
-    preceded(
-        terminated(game_id, tag(": ")),
-        separated_list1(tag("; "), draw),
-    )(input)
-}
-
-fn parse_input(input: &str) -> Vec<Vec<HashMap<Color, u32>>> {
-    separated_list1(tag("\n"), game)(input).unwrap().1
-}
-
-fn is_possible(game: &[HashMap<Color, u32>], content: &HashMap<Color, u32>) -> bool {
-    game.iter().all(|g| {
-        g.iter()
-            .all(|(color, &count)| count <= content.get(color).copied().unwrap_or_default())
-    })
-}
-
-fn solve_part1(games: &[Vec<HashMap<Color, u32>>], content: &HashMap<Color, u32>) -> usize {
-    games
+/// Writes cubes as a bag is given on the command line, e.g. `12 red, 14 blue`.
+fn describe(cubes: &Cubes) -> String {
+    let mut cubes: Vec<_> = cubes.iter().collect();
+    cubes.sort();
+    let cubes: Vec<_> = cubes
         .iter()
-        .enumerate()
-        .filter_map(|(i, g)| {
-            if is_possible(g, content) {
-                Some(i + 1)
-            } else {
-                None
-            }
-        })
-        .sum()
-}
-
-fn min_content(draws: &[HashMap<Color, u32>]) -> HashMap<Color, u32> {
-    draws.iter().fold(HashMap::new(), |mut acc, x| {
-        x.iter().for_each(|(color, &count)| {
-            let val = acc.entry(*color).or_default();
-            *val = count.max(*val);
-        });
-        acc
-    })
+        .map(|(color, count)| format!("{count} {color}"))
+        .collect();
+    cubes.join(", ")
 }
 
-fn power(content: &HashMap<Color, u32>) -> u32 {
-    [Color::Blue, Color::Green, Color::Red]
-        .iter()
-        .map(|c| content.get(c).copied().unwrap_or_default())
-        .product()
-}
+const USAGE: &str = "usage: day02 [\"12 red, 13 green, 14 blue\"] [--extra <k>] [--games <id,...>]";
 
-fn solve_part2(games: &[Vec<HashMap<Color, u32>>]) -> u32 {
-    games.iter().map(|g| power(&min_content(g))).sum()
+/// Prints `message` and how to run the binary, then exits.
+fn usage(message: &str) -> ! {
+    eprintln!("{message}\n{USAGE}");
+    process::exit(2)
 }
 
+/// Takes an optional bag such as `"12 red, 13 green, 14 blue"`, the puzzle's by default, then
+/// `--extra <k>` to find the best way to add k cubes to it and `--games <id,...>` to find the
+/// smallest bag that makes these games possible.
 fn main() {
+    let mut args = env::args().skip(1);
+    let mut bag = puzzle_bag();
+    let mut extra = None;
+    let mut ids = None;
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage(&format!("{arg} needs a value")))
+        };
+        match arg.as_str() {
+            "--extra" => {
+                let value = value();
+                let invalid = || usage(&format!("--extra takes a number, not {value}"));
+                extra = Some(value.parse::<u32>().unwrap_or_else(|_| invalid()))
+            }
+            "--games" => {
+                let value = value();
+                let invalid = || usage(&format!("--games takes game IDs, not {value}"));
+                ids = Some(
+                    value
+                        .split(',')
+                        .map(|id| id.trim().parse::<u32>().unwrap_or_else(|_| invalid()))
+                        .collect::<Vec<_>>(),
+                )
+            }
+            flag if flag.starts_with("--") => usage(&format!("unknown option {flag}")),
+            _ => bag = parse_bag(&arg).unwrap_or_else(|e| usage(&format!("{arg}: {e}"))),
+        }
+    }
     let input = include_str!("../../data/day02.txt");
-    let games = parse_input(input);
-    let content = HashMap::from([(Color::Blue, 14), (Color::Green, 13), (Color::Red, 12)]);
-    let answer1 = solve_part1(&games, &content);
+    let games = parse_input(input).unwrap();
+    let answer1 = solve_part1(&games, &bag);
     println!("The answer to part 1 is {}", answer1);
-    let answer2 = solve_part2(&games);
+    let answer2 = solve_part2(&games).unwrap();
     println!("The answer to part 2 is {}", answer2);
+    for game in &games {
+        for v in violations(game, &bag) {
+            println!(
+                "Game {}: draw {} shows {} {}, the bag holds {}",
+                game.id,
+                v.draw + 1,
+                v.drawn,
+                v.color,
+                v.available
+            );
+        }
+    }
+    if let Some(extra) = extra {
+        let (added, possible) = best_extra(&games, &bag, extra);
+        println!(
+            "Adding {} makes {possible} games possible",
+            if added.is_empty() {
+                "nothing".to_string()
+            } else {
+                describe(&added)
+            }
+        );
+    }
+    if let Some(ids) = ids {
+        let unknown: Vec<_> = ids
+            .iter()
+            .filter(|&&id| !games.iter().any(|g| g.id == id))
+            .map(u32::to_string)
+            .collect();
+        if !unknown.is_empty() {
+            eprintln!("no game has the ID {}", unknown.join(", "));
+            process::exit(1)
+        }
+        let bag = smallest_bag(games.iter().filter(|g| ids.contains(&g.id)));
+        let total: u32 = bag.values().sum();
+        println!(
+            "The smallest bag for these games holds {} ({total} cubes)",
+            describe(&bag)
+        );
+    }
 }
@@ -1,71 +1,76 @@
-type Number = u16;
+use std::{env, num::NonZeroUsize};
 
-struct Card {
-    winning_numbers: Vec<Number>,
-    hand: Vec<Number>,
-}
-
-fn parse_input(input: &str) -> Vec<Card> {
-    input
-        .lines()
-        .flat_map(|l| {
-            l.split_once(':').map(|(_, l)| {
-                l.trim()
-                    .split_once('|')
-                    .map(|(w, h)| Card {
-                        winning_numbers: w
-                            .trim()
-                            .split_whitespace()
-                            .map(|s| s.parse().unwrap())
-                            .collect(),
-                        hand: h
-                            .trim()
-                            .split_whitespace()
-                            .map(|s| s.parse().unwrap())
-                            .collect(),
-                    })
-                    .unwrap()
-            })
-        })
-        .collect()
-}
-
-impl Card {
-    fn win_count(&self) -> usize {
-        self.hand
-            .iter()
-            .filter(|n| self.winning_numbers.contains(n))
-            .count()
-    }
-}
-
-fn solve_part1(data: &[Card]) -> usize {
-    data.iter()
-        .map(|c| match c.win_count() {
-            0 => 0,
-            wins => 1 << (wins - 1),
-        })
-        .sum()
-}
-
-fn solve_part2(data: &[Card]) -> usize {
-    data.iter()
-        .fold((0, vec![1; data.len()]), |(sum, mut copies), c| {
-            let count = copies.pop().unwrap_or_default();
-            let wins = c.win_count();
-            copies.iter_mut().rev().take(wins).for_each(|c| {
-                *c += count;
-            });
-            (sum + count, copies)
-        })
-        .0
-}
+use aoc_2023::day04::{
+    doubling, fibonacci, linear, parse_input, report_with, score, solve_part1, solve_part2,
+    solve_part2_with, CopyRule, Scoring,
+};
 
+/// Takes `--scoring doubling|linear|fibonacci`, `--offset <n>` and `--cap <n>` to solve a
+/// variant of the puzzle too, and `--report` to list what every card wins under it.
 fn main() {
+    let mut args = env::args().skip(1);
+    let mut scoring: Option<Scoring> = None;
+    let mut rule = None;
+    let mut show_report = false;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| panic!("{arg} needs a value"));
+        match arg.as_str() {
+            "--scoring" => {
+                scoring = Some(match value().as_str() {
+                    "doubling" => doubling,
+                    "linear" => linear,
+                    "fibonacci" => fibonacci,
+                    other => panic!("unknown scoring {other}"),
+                })
+            }
+            "--offset" => {
+                let offset: NonZeroUsize =
+                    value().parse().expect("--offset takes a positive number");
+                rule = Some(CopyRule {
+                    offset,
+                    ..rule.unwrap_or_default()
+                });
+            }
+            "--cap" => {
+                let cap: NonZeroUsize = value().parse().expect("--cap takes a positive number");
+                rule = Some(CopyRule {
+                    cap: Some(cap),
+                    ..rule.unwrap_or_default()
+                });
+            }
+            "--report" => show_report = true,
+            _ => panic!("unknown argument {arg}"),
+        }
+    }
     let input = include_str!("../../data/day04.txt");
-    let data = parse_input(input);
-    let answer1 = solve_part1(&data);
+    let data = parse_input(input).unwrap();
+    let answer1 = solve_part1(&data).unwrap();
     println!("The answer to part 1 is {}", answer1);
-    let answer2 = solve_part2(&data);
+    let answer2 = solve_part2(&data).unwrap();
     println!("The answer to part 2 is {}", answer2);
+    if let Some(scoring) = scoring {
+        match score(&data, scoring) {
+            Some(points) => println!("The variant of part 1 scores {points}"),
+            None => println!("The variant of part 1 scores too many points to count"),
+        }
+    }
+    let rule = rule.unwrap_or_default();
+    if rule != CopyRule::default() {
+        match solve_part2_with(&data, &rule) {
+            Some(cards) => println!("The variant of part 2 wins {cards} cards"),
+            None => println!("The variant of part 2 wins too many cards to count"),
+        }
+    }
+    if show_report {
+        let report = report_with(&data, scoring.unwrap_or(doubling), &rule)
+            .expect("the variant wins too many cards to count");
+        println!("card  matches  points  copies");
+        for r in report {
+            let points = r.points.map_or("-".to_string(), |p| p.to_string());
+            println!(
+                "{:>4}  {:>7}  {:>6}  {:>6}",
+                r.id, r.matches, points, r.copies
+            );
+        }
+    }
 }
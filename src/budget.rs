@@ -0,0 +1,131 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Steps between two readings of the clock, which costs more than a step of most solvers.
+const CLOCK_INTERVAL: u64 = 1 << 10;
+
+/// A limit on the time and number of steps a solver may take.
+///
+/// Solvers that can run for very long, or forever, on unexpected input spend a step of the
+/// budget on each iteration of their main loop and give up once it is exhausted.
+#[derive(Debug, Clone)]
+pub struct Budget {
+    started: Instant,
+    time: Option<Duration>,
+    steps: Option<u64>,
+    spent: u64,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        Self {
+            started: Instant::now(),
+            time: None,
+            steps: None,
+            spent: 0,
+        }
+    }
+
+    /// Limits the time spent from now on to `time`.
+    pub fn with_time(self, time: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            time: Some(time),
+            ..self
+        }
+    }
+
+    /// Limits the number of steps to `steps`.
+    pub fn with_steps(self, steps: u64) -> Self {
+        Self {
+            steps: Some(steps),
+            ..self
+        }
+    }
+
+    /// The same limits, with nothing spent yet and the time counted from now.
+    pub fn renewed(&self) -> Self {
+        Self {
+            started: Instant::now(),
+            spent: 0,
+            ..*self
+        }
+    }
+
+    /// Spends a step, or reports how far the solver got, as described by `progress`, if the
+    /// budget is exhausted.
+    pub fn spend(&mut self, progress: impl FnOnce() -> String) -> Result<(), Timeout> {
+        self.spent += 1;
+        let out_of_steps = self.steps.is_some_and(|steps| self.spent > steps);
+        let out_of_time = self.spent.is_multiple_of(CLOCK_INTERVAL)
+            && self.time.is_some_and(|time| self.started.elapsed() > time);
+        if out_of_steps || out_of_time {
+            return Err(Timeout {
+                steps: self.spent - 1,
+                elapsed: self.started.elapsed(),
+                progress: progress(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The budget of a solver ran out before it found the answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeout {
+    pub steps: u64,
+    pub elapsed: Duration,
+    pub progress: String,
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "gave up after {} steps in {:.2?}, {}",
+            self.steps, self.elapsed, self.progress
+        )
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_steps() {
+        let mut budget = Budget::unlimited().with_steps(3);
+        for _ in 0..3 {
+            assert!(budget.spend(|| unreachable!()).is_ok());
+        }
+        let timeout = budget.spend(|| "at step 4".to_string()).unwrap_err();
+        assert_eq!((timeout.steps, timeout.progress.as_str()), (3, "at step 4"));
+    }
+
+    #[test]
+    fn test_time() {
+        let mut budget = Budget::unlimited().with_time(Duration::ZERO);
+        let spent = (1..=CLOCK_INTERVAL).find(|_| budget.spend(String::new).is_err());
+        assert_eq!(spent, Some(CLOCK_INTERVAL));
+        let mut budget = Budget::unlimited();
+        assert!((0..10 * CLOCK_INTERVAL).all(|_| budget.spend(String::new).is_ok()));
+    }
+
+    #[test]
+    fn test_renewed() {
+        let mut budget = Budget::unlimited().with_steps(1);
+        assert!(budget.spend(String::new).is_ok());
+        assert!(budget.clone().spend(String::new).is_err());
+        assert!(budget.renewed().spend(String::new).is_ok());
+    }
+}
@@ -0,0 +1,635 @@
+use std::{
+    fmt,
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+use crate::ParseError;
+
+/// The lines of `input`, whatever text they hold, without their `\n` or `\r\n` endings.
+pub fn parse_input(input: &str) -> Result<Vec<&str>, ParseError> {
+    Ok(input.lines().collect())
+}
+
+/// Accepts the documents whose every line holds a digit, spelled out in English or not, so that
+/// `aoc identify` can tell a calibration document from any other text, which
+/// [`parse_input`] accepts.
+pub(crate) fn check_format(input: &str) -> Result<(), ParseError> {
+    let decoder = CalibrationDecoder::english();
+    match input.lines().find(|line| decoder.decode(line).is_none()) {
+        Some(line) => Err(ParseError::at(input, line)),
+        None => Ok(()),
+    }
+}
+
+pub fn solve_part1(data: &[&str], policy: Policy) -> Result<u64, CalibrationError> {
+    CalibrationDecoder::numerals().sum(data, policy)
+}
+
+/// The digits 0 to 9 as numerals and 1 to 9 spelled out in English.
+const ENGLISH: [(&str, u32); 19] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// A vocabulary line that is neither a `word=value` pair, blank nor a `#` comment, or whose
+/// words would make decoding ambiguous.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VocabularyError {
+    Syntax {
+        line: usize,
+    },
+    Value {
+        line: usize,
+        value: String,
+    },
+    Duplicate {
+        line: usize,
+        word: String,
+    },
+    Conflict {
+        line: usize,
+        word: String,
+        value: u32,
+        previous: u32,
+    },
+    Ambiguous {
+        line: usize,
+        word: String,
+        prefix_of: String,
+    },
+}
+
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabularyError::Syntax { line } => write!(f, "line {line}: expected `word=value`"),
+            VocabularyError::Value { line, value } => {
+                write!(f, "line {line}: `{value}` is not a digit from 0 to 9")
+            }
+            VocabularyError::Duplicate { line, word } => {
+                write!(f, "line {line}: `{word}` is already defined")
+            }
+            VocabularyError::Conflict {
+                line,
+                word,
+                value,
+                previous,
+            } => {
+                write!(
+                    f,
+                    "line {line}: `{word}` stands for {previous}, not {value}"
+                )
+            }
+            VocabularyError::Ambiguous {
+                line,
+                word,
+                prefix_of,
+            } => {
+                write!(f, "line {line}: `{word}` is a prefix of `{prefix_of}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VocabularyError {}
+
+/// The words that stand for each digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// Numerals and digits spelled out in English.
+    pub fn english() -> Self {
+        Self {
+            words: ENGLISH
+                .iter()
+                .map(|&(word, value)| (word.to_string(), value))
+                .collect(),
+        }
+    }
+
+    /// The numerals 0 to 9 alone, as read by part 1.
+    pub fn numerals() -> Self {
+        Self {
+            words: ENGLISH[9..]
+                .iter()
+                .map(|&(word, value)| (word.to_string(), value))
+                .collect(),
+        }
+    }
+
+    /// Reads a vocabulary as [`FromStr`] does, but also rejects a word that is a prefix of
+    /// another, since "II" could then be read as "II" or as "I" twice.
+    pub fn parse_strict(s: &str) -> Result<Self, VocabularyError> {
+        Self::parse(s, true)
+    }
+
+    fn parse(s: &str, strict: bool) -> Result<Self, VocabularyError> {
+        let mut words: Vec<(String, u32)> = Vec::new();
+        for (line, text) in (1..).zip(s.lines()) {
+            let text = text.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let (word, value) = text
+                .split_once('=')
+                .map(|(word, value)| (word.trim(), value.trim()))
+                .filter(|(word, _)| !word.is_empty())
+                .ok_or(VocabularyError::Syntax { line })?;
+            let value = value
+                .parse()
+                .ok()
+                .filter(|&value| value <= 9)
+                .ok_or_else(|| VocabularyError::Value {
+                    line,
+                    value: value.to_string(),
+                })?;
+            if let Some(&(_, previous)) = words.iter().find(|(other, _)| other == word) {
+                let word = word.to_string();
+                return Err(if previous == value {
+                    VocabularyError::Duplicate { line, word }
+                } else {
+                    VocabularyError::Conflict {
+                        line,
+                        word,
+                        value,
+                        previous,
+                    }
+                });
+            }
+            if strict {
+                for (other, _) in &words {
+                    let (shorter, longer) = if other.len() < word.len() {
+                        (other.as_str(), word)
+                    } else {
+                        (word, other.as_str())
+                    };
+                    if longer.starts_with(shorter) {
+                        return Err(VocabularyError::Ambiguous {
+                            line,
+                            word: shorter.to_string(),
+                            prefix_of: longer.to_string(),
+                        });
+                    }
+                }
+            }
+            words.push((word.to_string(), value));
+        }
+        Ok(Self { words })
+    }
+}
+
+/// Reads one `word=value` pair per line, such as `zero=0`, skipping blank lines and `#`
+/// comments.
+///
+/// The same word cannot be defined twice, let alone with another value. A word may be a prefix
+/// of another, as "I" is of "II": the decoder reads the longest word.
+/// [`Vocabulary::parse_strict`] rejects such words instead.
+impl FromStr for Vocabulary {
+    type Err = VocabularyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, false)
+    }
+}
+
+/// What to do with a line that holds no digit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Stop at the line with a [`CalibrationError::NoDigit`].
+    #[default]
+    Fail,
+    /// Leave the line out of the sum.
+    Skip,
+}
+
+#[derive(Debug)]
+pub enum CalibrationError {
+    NoDigit { line: usize },
+    Io(io::Error),
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalibrationError::NoDigit { line } => write!(f, "line {line}: no digit"),
+            CalibrationError::Io(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+impl From<io::Error> for CalibrationError {
+    fn from(e: io::Error) -> Self {
+        CalibrationError::Io(e)
+    }
+}
+
+/// An occurrence of a word of the vocabulary in a line, starting at byte `position`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub position: usize,
+    pub word: String,
+    pub value: u32,
+}
+
+/// How a decoder read a line: every word it found, overlapping ones included, and the first
+/// and last digits it chose among them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineReport {
+    /// The number of the line, counting from 1.
+    pub line: usize,
+    /// The words found, by position.
+    pub tokens: Vec<Token>,
+    /// The first and last digits, or `None` if the line holds none.
+    pub digits: Option<(u32, u32)>,
+}
+
+impl LineReport {
+    /// The calibration value of the line.
+    pub fn value(&self) -> Result<u32, CalibrationError> {
+        let (first, last) = self
+            .digits
+            .ok_or(CalibrationError::NoDigit { line: self.line })?;
+        Ok(first * 10 + last)
+    }
+}
+
+/// Writes e.g. `line 3: two@0 1@3 nine@4 -> 29`, or `line 4: no digit`.
+impl fmt::Display for LineReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}:", self.line)?;
+        for token in &self.tokens {
+            write!(f, " {}@{}", token.word, token.position)?;
+        }
+        match self.value() {
+            Ok(value) => write!(f, " -> {value}"),
+            Err(_) => write!(f, " no digit"),
+        }
+    }
+}
+
+/// Finds the first and last digits of lines, recognising the words of a vocabulary.
+///
+/// The automata that match the words are built once, so that a decoder can be reused for any
+/// number of lines. Words may overlap, as in "oneight": the first digit is the word that starts
+/// first and the last digit the word that ends last, the longest one winning ties.
+#[derive(Debug, Clone)]
+pub struct CalibrationDecoder {
+    forward: AhoCorasick,
+    /// Matches the words spelled backwards, so that the leftmost match in a reversed line is
+    /// the rightmost one in the line.
+    backward: AhoCorasick,
+    /// Reports every occurrence of every word, for [`CalibrationDecoder::report`].
+    overlapping: AhoCorasick,
+    words: Vec<String>,
+    values: Vec<u32>,
+}
+
+fn automaton<P: AsRef<[u8]>>(kind: MatchKind, words: impl IntoIterator<Item = P>) -> AhoCorasick {
+    AhoCorasick::builder()
+        .match_kind(kind)
+        .build(words)
+        .expect("the vocabulary should fit in an automaton")
+}
+
+impl CalibrationDecoder {
+    /// A decoder that reads each word of `vocabulary` as the digit it stands for.
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let (words, values): (Vec<_>, Vec<_>) = vocabulary.words.iter().cloned().unzip();
+        Self {
+            forward: automaton(MatchKind::LeftmostLongest, &words),
+            backward: automaton(
+                MatchKind::LeftmostLongest,
+                words
+                    .iter()
+                    .map(|word| word.bytes().rev().collect::<Vec<_>>()),
+            ),
+            overlapping: automaton(MatchKind::Standard, &words),
+            words,
+            values,
+        }
+    }
+
+    /// A decoder for numerals and digits spelled out in English.
+    pub fn english() -> Self {
+        Self::new(&Vocabulary::english())
+    }
+
+    /// A decoder for the numerals 0 to 9 alone.
+    pub fn numerals() -> Self {
+        Self::new(&Vocabulary::numerals())
+    }
+
+    /// The first and last digits of `line`, which are the same if it holds a single one, or
+    /// `None` if it holds none.
+    pub fn decode(&self, line: &str) -> Option<(u32, u32)> {
+        let first = self.forward.find(line)?;
+        let reversed: Vec<u8> = line.bytes().rev().collect();
+        let last = self.backward.find(&reversed)?;
+        Some((self.values[first.pattern()], self.values[last.pattern()]))
+    }
+
+    /// Explains how `text`, the line numbered `line`, decodes.
+    pub fn report(&self, line: usize, text: &str) -> LineReport {
+        let mut tokens: Vec<Token> = self
+            .overlapping
+            .find_overlapping_iter(text)
+            .map(|m| Token {
+                position: m.start(),
+                word: self.words[m.pattern()].clone(),
+                value: self.values[m.pattern()],
+            })
+            .collect();
+        tokens.sort_by_key(|token| token.position);
+        LineReport {
+            line,
+            tokens,
+            digits: self.decode(text),
+        }
+    }
+
+    fn calibration_value(
+        &self,
+        line: usize,
+        text: &str,
+        policy: Policy,
+    ) -> Result<u32, CalibrationError> {
+        match (self.decode(text), policy) {
+            (Some((first, last)), _) => Ok(first * 10 + last),
+            (None, Policy::Skip) => Ok(0),
+            (None, Policy::Fail) => Err(CalibrationError::NoDigit { line }),
+        }
+    }
+
+    /// Sums the calibration values of `lines`, numbered from 1.
+    pub fn sum(&self, lines: &[&str], policy: Policy) -> Result<u64, CalibrationError> {
+        (1..)
+            .zip(lines)
+            .map(|(line, text)| self.calibration_value(line, text, policy).map(u64::from))
+            .sum()
+    }
+
+    /// Sums the calibration values of the lines read from `reader`, one line at a time so that
+    /// the document need not fit in memory.
+    pub fn sum_lines(
+        &self,
+        mut reader: impl BufRead,
+        policy: Policy,
+    ) -> Result<u64, CalibrationError> {
+        let mut text = String::new();
+        let mut sum = 0;
+        for line in 1.. {
+            if reader.read_line(&mut text)? == 0 {
+                break;
+            }
+            let text_line = text.strip_suffix('\n').unwrap_or(&text);
+            let text_line = text_line.strip_suffix('\r').unwrap_or(text_line);
+            sum += u64::from(self.calibration_value(line, text_line, policy)?);
+            text.clear();
+        }
+        Ok(sum)
+    }
+}
+
+pub fn solve_part2(data: &[&str], policy: Policy) -> Result<u64, CalibrationError> {
+    solve_part2_with(data, &CalibrationDecoder::english(), policy)
+}
+
+/// Solves part 2 with the digit words of the vocabulary of `decoder`.
+pub fn solve_part2_with(
+    data: &[&str],
+    decoder: &CalibrationDecoder,
+    policy: Policy,
+) -> Result<u64, CalibrationError> {
+    decoder.sum(data, policy)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve_part2() {
+        let data = parse_input(
+            "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n\
+             4nineeightseven2\nzoneight234\n7pqrstsixteen\n",
+        )
+        .unwrap();
+        assert_eq!(solve_part2(&data, Policy::Fail).unwrap(), 281);
+    }
+
+    #[test]
+    fn test_sum_lines() {
+        let decoder = CalibrationDecoder::english();
+        let document = "two1nine\nabcone2threexyz\n7pqrstsixteen\n".repeat(1000);
+        assert_eq!(
+            decoder
+                .sum_lines(document.as_bytes(), Policy::Fail)
+                .unwrap(),
+            (29 + 13 + 76) * 1000
+        );
+        let lines = parse_input(&document).unwrap();
+        assert_eq!(
+            decoder.sum(&lines, Policy::Fail).unwrap(),
+            (29 + 13 + 76) * 1000
+        );
+        assert_eq!(decoder.decode("nodigit"), None);
+        assert_eq!(
+            decoder
+                .sum_lines("two1nine\r\nz\u{e9}ro7\r\n".as_bytes(), Policy::Fail)
+                .unwrap(),
+            29 + 77
+        );
+    }
+
+    #[test]
+    fn test_parse_input_any_text() {
+        let data = parse_input("two1nine\r\nz\u{e9}ro 7!\r\n\r\n").unwrap();
+        assert_eq!(data, ["two1nine", "z\u{e9}ro 7!", ""]);
+        assert_eq!(solve_part1(&data, Policy::Skip).unwrap(), 11 + 77);
+    }
+
+    #[test]
+    fn test_no_digit() {
+        let data = parse_input("1abc2\npqr3stu8vwx\nnodigit\ntreb7uchet\n").unwrap();
+        assert!(matches!(
+            solve_part1(&data, Policy::Fail),
+            Err(CalibrationError::NoDigit { line: 3 })
+        ));
+        assert_eq!(solve_part1(&data, Policy::Skip).unwrap(), 12 + 38 + 77);
+        assert!(matches!(
+            solve_part2(&data, Policy::Fail),
+            Err(CalibrationError::NoDigit { line: 3 })
+        ));
+        let decoder = CalibrationDecoder::english();
+        assert_eq!(
+            decoder
+                .sum_lines("two\nx\n3\n".as_bytes(), Policy::Skip)
+                .unwrap(),
+            22 + 33
+        );
+        let error = decoder
+            .sum_lines("two\nx\n3\n".as_bytes(), Policy::Fail)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "line 2: no digit");
+    }
+
+    #[test]
+    fn test_report() {
+        let decoder = CalibrationDecoder::english();
+        let report = decoder.report(6, "zoneight234");
+        assert_eq!(report.digits, Some((1, 4)));
+        assert_eq!(
+            report
+                .tokens
+                .iter()
+                .map(|token| (token.position, token.value))
+                .collect::<Vec<_>>(),
+            [(1, 1), (3, 8), (8, 2), (9, 3), (10, 4)]
+        );
+        assert_eq!(
+            report.to_string(),
+            "line 6: one@1 eight@3 2@8 3@9 4@10 -> 14"
+        );
+        let report = decoder.report(2, "xyz");
+        assert!(report.tokens.is_empty());
+        assert!(matches!(
+            report.value(),
+            Err(CalibrationError::NoDigit { line: 2 })
+        ));
+        assert_eq!(report.to_string(), "line 2: no digit");
+    }
+
+    #[test]
+    fn test_vocabulary() {
+        let french: Vocabulary =
+            "# les chiffres\nzéro = 0\nun=1\ndeux=2\ntrois=3\n\nquatre=4\nsept=7\nneuf=9"
+                .parse()
+                .unwrap();
+        let decoder = CalibrationDecoder::new(&french);
+        let data = parse_input("deuxtwo4quatre\nxtroisunzéro\nneufàseptième\n").unwrap();
+        assert_eq!(
+            solve_part2_with(&data, &decoder, Policy::Fail).unwrap(),
+            24 + 30 + 97
+        );
+        let roman: Vocabulary = "I=1\nII=2\nIII=3\nIV=4\nV=5\nVI=6\nVII=7\nVIII=8\nIX=9"
+            .parse()
+            .unwrap();
+        let decoder = CalibrationDecoder::new(&roman);
+        assert_eq!(decoder.decode("IIIxVIII"), Some((3, 8)));
+        assert_eq!(decoder.decode("IIxIV"), Some((2, 4)));
+        assert_eq!(
+            Vocabulary::parse_strict("I=1\nII=2"),
+            Err(VocabularyError::Ambiguous {
+                line: 2,
+                word: "I".to_string(),
+                prefix_of: "II".to_string()
+            })
+        );
+        assert_eq!(
+            Vocabulary::parse_strict("# deux mots\nseize=6\nsei=6").unwrap_err(),
+            VocabularyError::Ambiguous {
+                line: 3,
+                word: "sei".to_string(),
+                prefix_of: "seize".to_string()
+            }
+        );
+        assert_eq!(
+            Vocabulary::parse_strict("un=1\ndeux=2"),
+            "un=1\ndeux=2".parse()
+        );
+        assert_eq!(
+            Vocabulary::parse_strict("un=1\nun=1"),
+            Err(VocabularyError::Duplicate {
+                line: 2,
+                word: "un".to_string()
+            })
+        );
+        assert_eq!(
+            "un=1\nmille".parse::<Vocabulary>(),
+            Err(VocabularyError::Syntax { line: 2 })
+        );
+        assert_eq!(
+            "dix=10".parse::<Vocabulary>(),
+            Err(VocabularyError::Value {
+                line: 1,
+                value: "10".to_string()
+            })
+        );
+        assert_eq!(
+            "un=1\nun=1".parse::<Vocabulary>(),
+            Err(VocabularyError::Duplicate {
+                line: 2,
+                word: "un".to_string()
+            })
+        );
+        assert_eq!(
+            "un=1\nune=1\nun=7".parse::<Vocabulary>(),
+            Err(VocabularyError::Conflict {
+                line: 3,
+                word: "un".to_string(),
+                value: 7,
+                previous: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_overlapping_words() {
+        let decoder = CalibrationDecoder::english();
+        let words = &ENGLISH[..9];
+        let mut overlaps = Vec::new();
+        for &(first, first_value) in words {
+            for &(last, last_value) in words {
+                for shared in 1..first.len().min(last.len()) {
+                    if first.ends_with(&last[..shared]) {
+                        let line = format!("{first}{}", &last[shared..]);
+                        assert_eq!(decoder.decode(&line), Some((first_value, last_value)));
+                        assert_eq!(
+                            decoder.decode(&format!("x{line}7{line}x")),
+                            Some((first_value, last_value))
+                        );
+                        overlaps.push(line);
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            overlaps,
+            [
+                "oneight",
+                "twone",
+                "threeight",
+                "fiveight",
+                "sevenine",
+                "eightwo",
+                "eighthree",
+                "nineight"
+            ]
+        );
+    }
+}
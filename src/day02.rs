@@ -0,0 +1,410 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap},
+};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, char, one_of},
+    combinator::{map, map_res, recognize},
+    multi::{many0, many1, separated_list1},
+    sequence::{preceded, separated_pair, terminated},
+    IResult,
+};
+
+use crate::{parse::finish, ParseError};
+
+/// The name of a colour of cubes, such as `red`.
+pub type Color = String;
+
+/// A number of cubes of each colour, drawn from a bag or held by one.
+pub type Cubes = HashMap<Color, u32>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<Cubes>,
+}
+
+/// The bag of the puzzle: 12 red, 13 green and 14 blue cubes.
+pub fn puzzle_bag() -> Cubes {
+    HashMap::from([
+        ("red".into(), 12),
+        ("green".into(), 13),
+        ("blue".into(), 14),
+    ])
+}
+
+fn color(input: &str) -> IResult<&str, Color> {
+    map(alpha1, String::from)(input)
+}
+
+fn integer(input: &str) -> IResult<&str, u32> {
+    map_res(
+        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+fn color_count(input: &str) -> IResult<&str, (Color, u32)> {
+    map(separated_pair(integer, tag(" "), color), |(a, b)| (b, a))(input)
+}
+
+fn draw(input: &str) -> IResult<&str, Cubes> {
+    map(separated_list1(tag(", "), color_count), |v| {
+        v.into_iter().collect()
+    })(input)
+}
+
+fn game_id(input: &str) -> IResult<&str, u32> {
+    preceded(tag("Game "), integer)(input)
+}
+
+fn game(input: &str) -> IResult<&str, Game> {
+    map(
+        separated_pair(game_id, tag(": "), separated_list1(tag("; "), draw)),
+        |(id, draws)| Game { id, draws },
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    finish(input, separated_list1(tag("\n"), game)(input))
+}
+
+/// Parses the content of a bag written as a draw, such as `12 red, 13 green, 14 blue`.
+pub fn parse_bag(input: &str) -> Result<Cubes, ParseError> {
+    finish(input, draw(input))
+}
+
+/// Whether every draw of `game` could have come out of a bag holding `content`.
+pub fn is_possible(game: &Game, content: &Cubes) -> bool {
+    game.draws.iter().all(|g| {
+        g.iter()
+            .all(|(color, &count)| count <= content.get(color).copied().unwrap_or_default())
+    })
+}
+
+pub fn solve_part1(games: &[Game], content: &Cubes) -> u64 {
+    games
+        .iter()
+        .filter(|g| is_possible(g, content))
+        .map(|g| u64::from(g.id))
+        .sum()
+}
+
+/// The fewest cubes of each colour a bag must hold for `game` to be possible.
+pub fn min_content(game: &Game) -> Cubes {
+    game.draws.iter().fold(HashMap::new(), |mut acc, x| {
+        x.iter().for_each(|(color, &count)| {
+            let val = acc.entry(color.clone()).or_default();
+            *val = count.max(*val);
+        });
+        acc
+    })
+}
+
+/// A colour of which a draw of a game shows more cubes than a bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The index of the draw in the game, from 0.
+    pub draw: usize,
+    pub color: Color,
+    pub drawn: u32,
+    pub available: u32,
+}
+
+/// The draws of `game` that a bag holding `content` could not have produced, in order.
+pub fn violations(game: &Game, content: &Cubes) -> Vec<Violation> {
+    let mut res: Vec<Violation> = game
+        .draws
+        .iter()
+        .enumerate()
+        .flat_map(|(draw, cubes)| {
+            cubes.iter().filter_map(move |(color, &drawn)| {
+                let available = available(content, color);
+                (drawn > available).then(|| Violation {
+                    draw,
+                    color: color.clone(),
+                    drawn,
+                    available,
+                })
+            })
+        })
+        .collect();
+    res.sort_by(|a, b| (a.draw, &a.color).cmp(&(b.draw, &b.color)));
+    res
+}
+
+/// The bag with the fewest cubes that makes all of `games` possible.
+pub fn smallest_bag<'a>(games: impl IntoIterator<Item = &'a Game>) -> Cubes {
+    games.into_iter().fold(HashMap::new(), |mut acc, game| {
+        for (color, count) in min_content(game) {
+            let val = acc.entry(color).or_default();
+            *val = count.max(*val);
+        }
+        acc
+    })
+}
+
+/// Largest number of combinations of extra cubes that [`best_extra`] tries one by one.
+const MAX_COMBINATIONS: usize = 1 << 16;
+
+/// How to add `extra` cubes to a bag holding `content` so that as many of `games` as possible
+/// become possible: the cubes to add, the fewest that reach the best outcome, and the number of
+/// games then possible, including those that already were.
+///
+/// Only the numbers of cubes some game lacks are worth trying for each colour, but every
+/// combination of them is, and they grow with the number of games to the power of the number
+/// of colours they lack. Beyond `MAX_COMBINATIONS` of them, the games are instead made possible
+/// one at a time, the one lacking the fewest cubes first, which may fall short of the best
+/// outcome.
+pub fn best_extra(games: &[Game], content: &Cubes, extra: u32) -> (Cubes, usize) {
+    let mut candidates: BTreeMap<Color, BTreeSet<u32>> = BTreeMap::new();
+    for game in games {
+        for (color, count) in min_content(game) {
+            let lacking = count.saturating_sub(available(content, &color));
+            if 0 < lacking && lacking <= extra {
+                candidates
+                    .entry(color)
+                    .or_insert_with(|| BTreeSet::from([0]))
+                    .insert(lacking);
+            }
+        }
+    }
+    let combinations = candidates
+        .values()
+        .try_fold(1usize, |acc, counts| acc.checked_mul(counts.len()));
+    if combinations.is_none_or(|combinations| combinations > MAX_COMBINATIONS) {
+        return greedy_extra(games, content, extra);
+    }
+    let candidates: Vec<_> = candidates.into_iter().collect();
+    let possible = games.iter().filter(|g| is_possible(g, content)).count();
+    let mut best = (Cubes::new(), possible);
+    search(
+        games,
+        content,
+        &candidates,
+        extra,
+        &mut content.clone(),
+        &mut best,
+    );
+    best
+}
+
+/// The cubes of `bag` beyond those of `content`.
+fn added(bag: &Cubes, content: &Cubes) -> Cubes {
+    bag.iter()
+        .filter_map(|(color, &count)| {
+            let added = count - available(content, color);
+            (added > 0).then(|| (color.clone(), added))
+        })
+        .collect()
+}
+
+/// Tries every number of extra cubes of the first of `candidates` within `budget`, then of
+/// the next ones, keeping in `best` the additions to `content` that make the most games
+/// possible, and of those the first with the fewest cubes.
+fn search(
+    games: &[Game],
+    content: &Cubes,
+    candidates: &[(Color, BTreeSet<u32>)],
+    budget: u32,
+    bag: &mut Cubes,
+    best: &mut (Cubes, usize),
+) {
+    let Some(((color, counts), rest)) = candidates.split_first() else {
+        let possible = games.iter().filter(|g| is_possible(g, bag)).count();
+        let added = added(bag, content);
+        let total = |cubes: &Cubes| cubes.values().sum::<u32>();
+        if (possible, Reverse(total(&added))) > (best.1, Reverse(total(&best.0))) {
+            *best = (added, possible);
+        }
+        return;
+    };
+    let base = available(content, color);
+    for &count in counts.iter().take_while(|&&count| count <= budget) {
+        bag.insert(color.clone(), base + count);
+        search(games, content, rest, budget - count, bag, best);
+    }
+    bag.insert(color.clone(), base);
+}
+
+/// Adds to `content`, while `extra` allows, the cubes lacking to the game that lacks the
+/// fewest, the first of them on ties, and returns the cubes added and the games then possible.
+fn greedy_extra(games: &[Game], content: &Cubes, extra: u32) -> (Cubes, usize) {
+    let needs: Vec<Cubes> = games.iter().map(min_content).collect();
+    let mut bag = content.clone();
+    let mut budget = u64::from(extra);
+    loop {
+        let cheapest = needs
+            .iter()
+            .map(|need| {
+                need.iter()
+                    .filter_map(|(color, &count)| {
+                        let lacking = count.saturating_sub(available(&bag, color));
+                        (lacking > 0).then(|| (color.clone(), lacking))
+                    })
+                    .collect::<Cubes>()
+            })
+            .filter(|lacking| !lacking.is_empty())
+            .map(|lacking| {
+                (
+                    lacking.values().map(|&n| u64::from(n)).sum::<u64>(),
+                    lacking,
+                )
+            })
+            .filter(|&(total, _)| total <= budget)
+            .min_by_key(|&(total, _)| total);
+        let Some((total, lacking)) = cheapest else {
+            break;
+        };
+        for (color, count) in lacking {
+            *bag.entry(color).or_default() += count;
+        }
+        budget -= total;
+    }
+    let possible = games.iter().filter(|g| is_possible(g, &bag)).count();
+    (added(&bag, content), possible)
+}
+
+fn available(content: &Cubes, color: &str) -> u32 {
+    content.get(color).copied().unwrap_or_default()
+}
+
+/// The colours drawn in any of `games`.
+fn colors(games: &[Game]) -> BTreeSet<&str> {
+    games
+        .iter()
+        .flat_map(|g| g.draws.iter().flat_map(|d| d.keys().map(String::as_str)))
+        .collect()
+}
+
+/// The product of the numbers of cubes of each of `colors`, zero if one is missing, or `None`
+/// if it does not fit in a `u64`.
+fn power<'a>(content: &Cubes, colors: impl IntoIterator<Item = &'a str>) -> Option<u64> {
+    colors.into_iter().try_fold(1u64, |acc, c| {
+        acc.checked_mul(u64::from(available(content, c)))
+    })
+}
+
+/// Sums the powers of the games over every colour drawn in any of them, so that a game that
+/// never shows one of these colours has a power of zero, or returns `None` if the sum does not
+/// fit in a `u64`.
+pub fn solve_part2(games: &[Game]) -> Option<u64> {
+    let colors = colors(games);
+    games.iter().try_fold(0u64, |acc, g| {
+        acc.checked_add(power(&min_content(g), colors.iter().copied())?)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn test_solve() {
+        let games = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part1(&games, &puzzle_bag()), 8);
+        assert_eq!(solve_part2(&games), Some(2286));
+    }
+
+    #[test]
+    fn test_what_if() {
+        let games = parse_input(EXAMPLE).unwrap();
+        let bag = puzzle_bag();
+        assert_eq!(
+            violations(&games[2], &bag),
+            [Violation {
+                draw: 0,
+                color: "red".into(),
+                drawn: 20,
+                available: 12
+            }]
+        );
+        assert!(violations(&games[0], &bag).is_empty());
+        assert_eq!(
+            smallest_bag(&games[..2]),
+            HashMap::from([("red".into(), 4), ("green".into(), 3), ("blue".into(), 6)])
+        );
+        assert_eq!(best_extra(&games, &bag, 0), (HashMap::new(), 3));
+        assert_eq!(
+            best_extra(&games, &bag, 3),
+            (HashMap::from([("blue".into(), 1), ("red".into(), 2)]), 4)
+        );
+        assert_eq!(best_extra(&games, &bag, 9).1, 5);
+        let games =
+            parse_input("Game 1: 2 amber\nGame 2: 2 amber\nGame 3: 7 blue\nGame 4: 7 blue\n")
+                .unwrap();
+        assert_eq!(
+            best_extra(&games, &HashMap::new(), 7),
+            (HashMap::from([("amber".into(), 2)]), 2)
+        );
+        assert_eq!(parse_bag("12 red, 13 green, 14 blue"), Ok(bag));
+    }
+
+    #[test]
+    fn test_best_extra_many_colors() {
+        // Each game lacks one cube of its own colour and the last one of all of them, so that
+        // trying every combination would take 2^40 bags.
+        let colors: Vec<String> = (0..40)
+            .map(|i| {
+                format!(
+                    "c{}{}",
+                    char::from(b'a' + i / 26),
+                    char::from(b'a' + i % 26)
+                )
+            })
+            .collect();
+        let mut input: String = (1..)
+            .zip(&colors)
+            .map(|(id, color)| format!("Game {id}: 1 {color}\n"))
+            .collect();
+        let all: Vec<_> = colors.iter().map(|color| format!("1 {color}")).collect();
+        input.push_str(&format!("Game 41: {}\n", all.join(", ")));
+        let games = parse_input(&input).unwrap();
+        let (added, possible) = best_extra(&games, &HashMap::new(), 10);
+        assert_eq!(
+            (added.len(), added.values().sum::<u32>(), possible),
+            (10, 10, 10)
+        );
+        assert_eq!(
+            added.keys().collect::<BTreeSet<_>>(),
+            colors[..10].iter().collect()
+        );
+        assert_eq!(best_extra(&games, &HashMap::new(), 40).1, 41);
+        assert_eq!(best_extra(&games, &HashMap::new(), 0), (HashMap::new(), 0));
+    }
+
+    #[test]
+    fn test_ids_and_colors() {
+        let games = parse_input(
+            "Game 7: 2 teal, 1 red\nGame 3: 1 red; 5 teal\nGame 12: 3 mauve, 2 teal, 2 red\n",
+        )
+        .unwrap();
+        assert_eq!(games.iter().map(|g| g.id).collect::<Vec<_>>(), [7, 3, 12]);
+        let bag = HashMap::from([("teal".into(), 2), ("red".into(), 2)]);
+        assert_eq!(solve_part1(&games, &bag), 7);
+        assert_eq!(solve_part2(&games), Some(3 * 2 * 2));
+    }
+
+    #[test]
+    fn test_large_powers() {
+        let games = parse_input("Game 0: 333 H; 33333333 G").unwrap();
+        assert_eq!(solve_part2(&games), Some(333 * 33333333));
+        let games =
+            parse_input("Game 4294967295: 4294967295 a, 4294967295 b\nGame 1: 1 a").unwrap();
+        let bag = HashMap::from([("a".into(), u32::MAX), ("b".into(), u32::MAX)]);
+        assert_eq!(solve_part1(&games, &bag), 1 << 32);
+        assert_eq!(solve_part2(&games), Some(u64::from(u32::MAX).pow(2)));
+        let games = parse_input("Game 1: 4294967295 a, 4294967295 b, 2 c").unwrap();
+        assert_eq!(solve_part2(&games), None);
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use crate::{
+    geometry::{Dir4, Dir8, Point2},
+    interval::Interval,
+    stats::Stat,
+    ParseError,
+};
+
+/// A cell of the schematic, by row and column.
+pub type Location = Point2<isize>;
+
+type PartId = u32;
+
+/// A number of the schematic where it is written, on `row` over `columns`, so that equal
+/// numbers remain distinct parts and leading zeros count in its width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Number {
+    pub value: PartId,
+    pub row: isize,
+    pub columns: Interval<isize>,
+}
+
+impl Number {
+    fn locations(&self) -> impl Iterator<Item = Location> {
+        let row = self.row;
+        (self.columns.start..self.columns.end).map(move |column| Location::new(row, column))
+    }
+}
+
+#[derive(Debug)]
+pub struct Schematic {
+    symbols: HashMap<Location, char>,
+    numbers: Vec<Number>,
+}
+
+pub fn parse_input(input: &str) -> Result<Schematic, ParseError> {
+    if let Some(pos) =
+        input.find(|c: char| (c.is_ascii_alphabetic() || !c.is_ascii_graphic()) && c != '\n')
+    {
+        return Err(ParseError::at(input, &input[pos..]));
+    }
+    let symbols = input
+        .lines()
+        .enumerate()
+        .flat_map(|(i, l)| {
+            l.chars()
+                .enumerate()
+                .filter(|(_, c)| *c != '.' && !c.is_ascii_digit())
+                .map(move |(j, c)| (Location::new(i as isize, j as isize), c))
+        })
+        .collect();
+    let mut numbers = Vec::new();
+    for (row, line) in input.lines().enumerate() {
+        let mut rest = line;
+        while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+            let digits = &rest[start..];
+            let len = digits
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(digits.len());
+            let text = &digits[..len];
+            let column = (line.len() - digits.len()) as isize;
+            numbers.push(Number {
+                value: text.parse().map_err(|_| ParseError::at(input, text))?,
+                row: row as isize,
+                columns: Interval::new(column, column + len as isize),
+            });
+            rest = &digits[len..];
+        }
+    }
+    Ok(Schematic { symbols, numbers })
+}
+
+/// The cells around a cell that count as adjacent to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// The cells above, below, left and right.
+    Four,
+    /// These and the diagonal ones, as in the puzzle.
+    Eight,
+}
+
+impl Connectivity {
+    fn neighbors(self, loc: Location) -> Vec<Location> {
+        match self {
+            Connectivity::Four => Dir4::ALL.iter().map(|d| loc + d.offset()).collect(),
+            Connectivity::Eight => Dir8::ALL.iter().map(|d| loc + d.offset()).collect(),
+        }
+    }
+}
+
+/// The symbols a query looks at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolClass {
+    Any,
+    AnyOf(Vec<char>),
+}
+
+impl SymbolClass {
+    fn matches(&self, symbol: char) -> bool {
+        match self {
+            SymbolClass::Any => true,
+            SymbolClass::AnyOf(symbols) => symbols.contains(&symbol),
+        }
+    }
+}
+
+/// How many parts a symbol must be adjacent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+}
+
+impl Arity {
+    fn admits(self, count: usize) -> bool {
+        match self {
+            Arity::Exactly(n) => count == n,
+            Arity::AtLeast(n) => count >= n,
+            Arity::AtMost(n) => count <= n,
+        }
+    }
+}
+
+/// Selects the symbols of a class adjacent to a number of parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub symbols: SymbolClass,
+    pub arity: Arity,
+    pub connectivity: Connectivity,
+}
+
+impl Query {
+    /// The `*` adjacent to exactly two parts, which the puzzle calls gears.
+    pub fn gears() -> Self {
+        Self {
+            symbols: SymbolClass::AnyOf(vec!['*']),
+            arity: Arity::Exactly(2),
+            connectivity: Connectivity::Eight,
+        }
+    }
+}
+
+/// A symbol selected by a query, with the parts adjacent to it in reading order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolMatch {
+    pub location: Location,
+    pub symbol: char,
+    pub parts: Vec<Number>,
+}
+
+/// Parts linked to one another through the symbols they share, directly or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster {
+    /// The parts in reading order.
+    pub parts: Vec<Number>,
+    /// The symbols in reading order.
+    pub symbols: Vec<(Location, char)>,
+}
+
+impl Schematic {
+    /// The indices of the numbers adjacent to each symbol of `symbols`, in reading order
+    /// and told apart by index, as two parts may hold the same number.
+    fn adjacency(
+        &self,
+        symbols: &SymbolClass,
+        connectivity: Connectivity,
+    ) -> BTreeMap<Location, Vec<usize>> {
+        let mut res: BTreeMap<Location, Vec<usize>> = self
+            .symbols
+            .iter()
+            .filter(|(_, &symbol)| symbols.matches(symbol))
+            .map(|(&loc, _)| (loc, Vec::new()))
+            .collect();
+        for (index, number) in self.numbers.iter().enumerate() {
+            for location in number.locations() {
+                for loc in connectivity.neighbors(location) {
+                    if let Some(parts) = res.get_mut(&loc) {
+                        if parts.last() != Some(&index) {
+                            parts.push(index);
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// The numbers adjacent to at least one symbol of `symbols`, in reading order.
+    pub fn parts(&self, symbols: &SymbolClass, connectivity: Connectivity) -> Vec<Number> {
+        let mut indices: Vec<usize> = self
+            .adjacency(symbols, connectivity)
+            .into_values()
+            .flatten()
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|i| self.numbers[i]).collect()
+    }
+
+    /// The symbols that `query` selects, in reading order.
+    pub fn symbol_matches(&self, query: &Query) -> Vec<SymbolMatch> {
+        self.adjacency(&query.symbols, query.connectivity)
+            .into_iter()
+            .filter(|(_, parts)| query.arity.admits(parts.len()))
+            .map(|(location, parts)| SymbolMatch {
+                location,
+                symbol: self.symbols[&location],
+                parts: parts.into_iter().map(|i| self.numbers[i]).collect(),
+            })
+            .collect()
+    }
+
+    /// The parts adjacent to a symbol of `symbols`, grouped into the components of the graph
+    /// that links each part to these symbols, in the reading order of their first part.
+    pub fn clusters(&self, symbols: &SymbolClass, connectivity: Connectivity) -> Vec<Cluster> {
+        let adjacency = self.adjacency(symbols, connectivity);
+        let mut part_symbols = vec![Vec::new(); self.numbers.len()];
+        for (&loc, parts) in &adjacency {
+            for &part in parts {
+                part_symbols[part].push(loc);
+            }
+        }
+        let mut seen = vec![false; self.numbers.len()];
+        let mut res = Vec::new();
+        for start in 0..self.numbers.len() {
+            if seen[start] || part_symbols[start].is_empty() {
+                continue;
+            }
+            seen[start] = true;
+            let mut parts = vec![start];
+            let mut symbols = Vec::new();
+            let mut queue = VecDeque::from([start]);
+            while let Some(part) = queue.pop_front() {
+                for loc in &part_symbols[part] {
+                    if symbols.contains(loc) {
+                        continue;
+                    }
+                    symbols.push(*loc);
+                    for &other in &adjacency[loc] {
+                        if !seen[other] {
+                            seen[other] = true;
+                            parts.push(other);
+                            queue.push_back(other);
+                        }
+                    }
+                }
+            }
+            parts.sort_unstable();
+            symbols.sort_unstable();
+            res.push(Cluster {
+                parts: parts.into_iter().map(|i| self.numbers[i]).collect(),
+                symbols: symbols
+                    .into_iter()
+                    .map(|loc| (loc, self.symbols[&loc]))
+                    .collect(),
+            });
+        }
+        res
+    }
+}
+
+pub fn solve_part1(schematic: &Schematic) -> u64 {
+    schematic
+        .parts(&SymbolClass::Any, Connectivity::Eight)
+        .iter()
+        .fold(0u64, |acc, part| checked!(acc + (u64::from(part.value))))
+}
+
+/// Sums the gear ratios, or returns `None` if the sum does not fit in a `u64`.
+pub fn solve_part2(schematic: &Schematic) -> Option<u64> {
+    schematic
+        .symbol_matches(&Query::gears())
+        .iter()
+        .try_fold(0u64, |acc, gear| {
+            let ratio = gear
+                .parts
+                .iter()
+                .try_fold(1u64, |acc, part| acc.checked_mul(part.value.into()))?;
+            acc.checked_add(ratio)
+        })
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let schematic = parse_input(input)?;
+    let clusters = schematic.clusters(&SymbolClass::Any, Connectivity::Eight);
+    let largest = clusters.iter().rev().max_by_key(|c| c.parts.len());
+    let parts: usize = clusters.iter().map(|c| c.parts.len()).sum();
+    Ok(vec![
+        ("numbers", schematic.numbers.len().to_string()),
+        ("symbols", schematic.symbols.len().to_string()),
+        ("parts", parts.to_string()),
+        ("part clusters", clusters.len().to_string()),
+        (
+            "largest cluster",
+            largest.map_or("none".to_string(), |c| {
+                format!(
+                    "{} parts, {} symbols, first at {}",
+                    c.parts.len(),
+                    c.symbols.len(),
+                    c.symbols[0].0
+                )
+            }),
+        ),
+        (
+            "gears",
+            schematic.symbol_matches(&Query::gears()).len().to_string(),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let schematic = parse_input(
+            "467..114..\n...*......\n..35..633.\n......#...\n617*......\n\
+             .....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..\n",
+        )
+        .unwrap();
+        assert_eq!(solve_part1(&schematic), 4361);
+        assert_eq!(solve_part2(&schematic), Some(467835));
+    }
+
+    #[test]
+    fn test_queries() {
+        let schematic = parse_input("1.2.3\n.*.#.\n4.5..\n....6\n....-\n").unwrap();
+        let values = |parts: &[Number]| parts.iter().map(|p| p.value).collect::<Vec<_>>();
+        assert_eq!(
+            values(&schematic.parts(&SymbolClass::Any, Connectivity::Four)),
+            [6]
+        );
+        let stars = |arity| Query {
+            symbols: SymbolClass::AnyOf(vec!['*']),
+            arity,
+            connectivity: Connectivity::Eight,
+        };
+        let matches = schematic.symbol_matches(&stars(Arity::AtLeast(3)));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            (matches[0].location, values(&matches[0].parts)),
+            (Location::new(1, 1), vec![1, 2, 4, 5])
+        );
+        assert!(schematic
+            .symbol_matches(&stars(Arity::AtMost(3)))
+            .is_empty());
+        let any = |arity| Query {
+            symbols: SymbolClass::Any,
+            ..stars(arity)
+        };
+        assert_eq!(schematic.symbol_matches(&any(Arity::Exactly(3))).len(), 1);
+        assert_eq!(schematic.symbol_matches(&any(Arity::Exactly(1))).len(), 1);
+        let clusters = schematic.clusters(&SymbolClass::Any, Connectivity::Eight);
+        assert_eq!(
+            clusters
+                .iter()
+                .map(|c| values(&c.parts))
+                .collect::<Vec<_>>(),
+            [vec![1, 2, 3, 4, 5], vec![6]]
+        );
+        assert_eq!(
+            clusters[0].symbols,
+            [(Location::new(1, 1), '*'), (Location::new(1, 3), '#')]
+        );
+    }
+
+    #[test]
+    fn test_numbers_by_location() {
+        let schematic = parse_input("35.35\n..*..\n").unwrap();
+        assert_eq!(solve_part1(&schematic), 70);
+        assert_eq!(solve_part2(&schematic), Some(35 * 35));
+        let schematic = parse_input("007..\n...*.\n..2..\n").unwrap();
+        assert_eq!(
+            schematic.numbers[0],
+            Number {
+                value: 7,
+                row: 0,
+                columns: Interval::new(0, 3)
+            }
+        );
+        assert_eq!(solve_part1(&schematic), 9);
+        assert_eq!(solve_part2(&schematic), Some(14));
+    }
+
+    #[test]
+    fn test_large_ratios() {
+        let schematic = parse_input("30*303310331").unwrap();
+        assert_eq!(solve_part1(&schematic), 303310361);
+        assert_eq!(solve_part2(&schematic), Some(30 * 303310331));
+        let schematic =
+            parse_input("4294967295*4294967295\n.....................\n4294967295*4294967295")
+                .unwrap();
+        assert_eq!(solve_part1(&schematic), 4 * u64::from(u32::MAX));
+        assert_eq!(solve_part2(&schematic), None);
+    }
+}
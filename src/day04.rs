@@ -0,0 +1,335 @@
+use std::num::NonZeroUsize;
+
+use crate::ParseError;
+
+type Number = u16;
+
+/// A set of numbers held as one bit per number, so that matching a hand against it takes one
+/// lookup per number.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NumberSet {
+    words: Vec<u64>,
+}
+
+impl NumberSet {
+    pub fn contains(&self, n: Number) -> bool {
+        let n = usize::from(n);
+        self.words
+            .get(n / 64)
+            .is_some_and(|word| word & (1 << (n % 64)) != 0)
+    }
+
+    pub fn insert(&mut self, n: Number) {
+        let n = usize::from(n);
+        if self.words.len() <= n / 64 {
+            self.words.resize(n / 64 + 1, 0);
+        }
+        self.words[n / 64] |= 1 << (n % 64);
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+}
+
+impl FromIterator<Number> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = Number>>(iter: I) -> Self {
+        let mut res = Self::default();
+        iter.into_iter().for_each(|n| res.insert(n));
+        res
+    }
+}
+
+pub struct Card {
+    pub id: u32,
+    winning_numbers: NumberSet,
+    hand: Vec<Number>,
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Card>, ParseError> {
+    let numbers = |list: &str| {
+        list.split_whitespace()
+            .map(|s| s.parse().map_err(|_| ParseError::at(input, s)))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    input
+        .lines()
+        .map(|l| {
+            let (head, l) = l.split_once(':').ok_or_else(|| ParseError::at(input, l))?;
+            let id = head
+                .strip_prefix("Card")
+                .filter(|id| id.starts_with(' '))
+                .and_then(|id| id.trim_start().parse().ok())
+                .ok_or_else(|| ParseError::at(input, head))?;
+            let (w, h) = l.split_once('|').ok_or_else(|| ParseError::at(input, l))?;
+            Ok(Card {
+                id,
+                winning_numbers: numbers(w)?.into_iter().collect(),
+                hand: numbers(h)?,
+            })
+        })
+        .collect()
+}
+
+impl Card {
+    /// The numbers of the hand that are winning numbers, a number the hand holds twice
+    /// counting twice.
+    fn win_count(&self) -> usize {
+        self.hand
+            .iter()
+            .filter(|&&n| self.winning_numbers.contains(n))
+            .count()
+    }
+}
+
+/// Turns the number of matches of a card into points, or `None` if they do not fit in a
+/// `usize`.
+pub type Scoring = fn(usize) -> Option<usize>;
+
+/// 1 point for the first match, doubled by every other one, as in the puzzle.
+pub fn doubling(matches: usize) -> Option<usize> {
+    match matches {
+        0 => Some(0),
+        matches => 1usize.checked_shl((matches - 1).try_into().ok()?),
+    }
+}
+
+/// 1 point per match.
+pub fn linear(matches: usize) -> Option<usize> {
+    Some(matches)
+}
+
+/// The Fibonacci numbers 1, 1, 2, 3, 5... for 1, 2, 3, 4, 5... matches.
+pub fn fibonacci(matches: usize) -> Option<usize> {
+    match matches {
+        0 => Some(0),
+        matches => (1..matches)
+            .try_fold((0usize, 1usize), |(a, b), _| Some((b, a.checked_add(b)?)))
+            .map(|(_, b)| b),
+    }
+}
+
+/// The points of all cards, or `None` if they do not fit in a `usize`.
+pub fn solve_part1(data: &[Card]) -> Option<usize> {
+    score(data, doubling)
+}
+
+/// The points of all cards under `scoring`, or `None` if they do not fit in a `usize`.
+pub fn score(data: &[Card], scoring: Scoring) -> Option<usize> {
+    data.iter()
+        .try_fold(0usize, |acc, c| acc.checked_add(scoring(c.win_count())?))
+}
+
+/// The number of copies of each card, the original included, once every win has copied the
+/// cards that follow, or `None` if one of them does not fit in a `usize`.
+fn copies(data: &[Card]) -> Option<Vec<usize>> {
+    let mut copies = vec![1usize; data.len()];
+    for (i, c) in data.iter().enumerate() {
+        let count = copies[i];
+        let wins = c.win_count();
+        for c in copies.iter_mut().skip(i + 1).take(wins) {
+            *c = c.checked_add(count)?;
+        }
+    }
+    Some(copies)
+}
+
+/// The number of cards won, or `None` if it does not fit in a `usize`.
+pub fn solve_part2(data: &[Card]) -> Option<usize> {
+    copies(data)?
+        .into_iter()
+        .try_fold(0usize, usize::checked_add)
+}
+
+/// Which cards the matches of a card copy, and how many copies of a card can be won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyRule {
+    /// How far after a card the first card its matches copy is, 1 for the next card. The
+    /// following cards are copied too, one per match.
+    pub offset: NonZeroUsize,
+    /// The most copies of a card, the original included, beyond which wins copy it no more.
+    pub cap: Option<NonZeroUsize>,
+}
+
+/// The rule of the puzzle: each match copies one of the next cards, without limit.
+impl Default for CopyRule {
+    fn default() -> Self {
+        Self {
+            offset: NonZeroUsize::MIN,
+            cap: None,
+        }
+    }
+}
+
+/// The number of copies of each card, the original included, once every card has copied,
+/// in order, the cards that `rule` designates as many times as there are copies of it, or
+/// `None` if one of these numbers does not fit in a `usize`.
+///
+/// With the default rule this counts what `solve_part2` does, which the fuzz target of the
+/// day checks on arbitrary cards.
+pub fn cascade(data: &[Card], rule: &CopyRule) -> Option<Vec<usize>> {
+    let mut copies = vec![1usize; data.len()];
+    for (i, c) in data.iter().enumerate() {
+        let count = copies[i];
+        let wins = c.win_count();
+        for copied in copies.iter_mut().skip(i + rule.offset.get()).take(wins) {
+            *copied = match rule.cap {
+                Some(cap) => copied.saturating_add(count).min(cap.get()),
+                None => copied.checked_add(count)?,
+            };
+        }
+    }
+    Some(copies)
+}
+
+/// Solves part 2 with the copies that `rule` makes, or returns `None` if the number of cards
+/// won does not fit in a `usize`.
+pub fn solve_part2_with(data: &[Card], rule: &CopyRule) -> Option<usize> {
+    cascade(data, rule)?
+        .into_iter()
+        .try_fold(0usize, usize::checked_add)
+}
+
+/// What a card wins, in both parts of the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CardReport {
+    pub id: u32,
+    pub matches: usize,
+    /// The points of the card, or `None` if they do not fit in a `usize`.
+    pub points: Option<usize>,
+    /// The copies of the card held at the end of part 2, the original included.
+    pub copies: usize,
+}
+
+/// Reports on every card, in order, or returns `None` if the copies of one do not fit in a
+/// `usize`.
+pub fn report(data: &[Card]) -> Option<Vec<CardReport>> {
+    report_with(data, doubling, &CopyRule::default())
+}
+
+/// Reports on every card, in order, scored by `scoring` and copied by `rule`, or returns
+/// `None` if the copies of one do not fit in a `usize`.
+pub fn report_with(data: &[Card], scoring: Scoring, rule: &CopyRule) -> Option<Vec<CardReport>> {
+    let copies = cascade(data, rule)?;
+    let res = data
+        .iter()
+        .zip(copies)
+        .map(|(c, copies)| {
+            let matches = c.win_count();
+            CardReport {
+                id: c.id,
+                matches,
+                points: scoring(matches),
+                copies,
+            }
+        })
+        .collect();
+    Some(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn test_solve() {
+        let cards = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part1(&cards), Some(13));
+        assert_eq!(solve_part2(&cards), Some(30));
+    }
+
+    #[test]
+    fn test_report() {
+        let cards = parse_input(&EXAMPLE.replace("Card 6", "Card 60")).unwrap();
+        let report = report(&cards).unwrap();
+        assert_eq!(
+            report
+                .iter()
+                .map(|r| (r.id, r.matches, r.points.unwrap(), r.copies))
+                .collect::<Vec<_>>(),
+            [
+                (1, 4, 8, 1),
+                (2, 2, 2, 2),
+                (3, 2, 2, 4),
+                (4, 1, 1, 8),
+                (5, 0, 0, 14),
+                (60, 0, 0, 1)
+            ]
+        );
+        assert!(parse_input("Cards 1: 1 | 1").is_err());
+        assert!(parse_input("Card: 1 | 1").is_err());
+    }
+
+    #[test]
+    fn test_rules() {
+        let cards = parse_input(EXAMPLE).unwrap();
+        assert_eq!(score(&cards, linear), Some(4 + 2 + 2 + 1));
+        assert_eq!(
+            (0..=8).map(|n| fibonacci(n).unwrap()).collect::<Vec<_>>(),
+            [0, 1, 1, 2, 3, 5, 8, 13, 21]
+        );
+        assert_eq!(score(&cards, fibonacci), Some(3 + 1 + 1 + 1));
+        assert_eq!(cascade(&cards, &CopyRule::default()), copies(&cards));
+        let skip_one = CopyRule {
+            offset: NonZeroUsize::new(2).unwrap(),
+            cap: None,
+        };
+        assert_eq!(cascade(&cards, &skip_one).unwrap(), [1, 1, 2, 3, 5, 7]);
+        let capped = CopyRule {
+            cap: NonZeroUsize::new(4),
+            ..CopyRule::default()
+        };
+        assert_eq!(cascade(&cards, &capped).unwrap(), [1, 2, 4, 4, 4, 1]);
+        assert_eq!(solve_part2_with(&cards, &capped), Some(16));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(doubling(64), Some(1 << 63));
+        assert_eq!(doubling(65), None);
+        assert_eq!(fibonacci(93), Some(12_200_160_415_121_876_738));
+        assert_eq!(fibonacci(94), None);
+        let cards = parse_input(&format!("Card 1: 0 | {}", "0 ".repeat(65))).unwrap();
+        assert_eq!(solve_part1(&cards), None);
+        assert_eq!(score(&cards, linear), Some(65));
+        let report = report(&cards).unwrap();
+        assert_eq!((report[0].matches, report[0].points), (65, None));
+        let hand = "1 ".repeat(70);
+        let cards: String = (1..=70)
+            .map(|i| format!("Card {i}: 1 | {hand}\n"))
+            .collect();
+        let cards = parse_input(&cards).unwrap();
+        assert_eq!(solve_part2(&cards), None);
+        assert_eq!(cascade(&cards, &CopyRule::default()), copies(&cards));
+        let capped = CopyRule {
+            cap: Some(NonZeroUsize::MAX),
+            ..CopyRule::default()
+        };
+        assert_eq!(solve_part2_with(&cards, &capped), None);
+        assert!(cascade(&cards, &capped).is_some());
+    }
+
+    #[test]
+    fn test_number_set() {
+        let set: NumberSet = [3, 64, 999, 3].into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(64) && set.contains(999) && !set.contains(65));
+        assert!(!set.contains(Number::MAX) && NumberSet::default().is_empty());
+    }
+}
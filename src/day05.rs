@@ -0,0 +1,260 @@
+use std::cmp::Ordering;
+
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, none_of, one_of},
+    combinator::{map, map_opt, map_res, recognize},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+
+use crate::{
+    interval::{Interval, IntervalSet},
+    parse::finish,
+    ParseError,
+};
+
+type Id = u64;
+
+/// A range of `range` ids from `source` on mapped to as many from `target` on, neither range
+/// going past `Id::MAX`, which [`entry`] checks.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    source: Id,
+    target: Id,
+    range: Id,
+}
+
+pub struct Mapping {
+    entries: Vec<Entry>,
+}
+
+impl Mapping {
+    fn new(mut entries: Vec<Entry>) -> Self {
+        entries.sort_unstable();
+        Self { entries }
+    }
+}
+
+fn integer(input: &str) -> IResult<&str, Id> {
+    map_res(
+        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+fn entry(input: &str) -> IResult<&str, Entry> {
+    map_opt(
+        tuple((
+            integer,
+            preceded(tag(" "), integer),
+            preceded(tag(" "), integer),
+        )),
+        |(target, source, range)| {
+            source.checked_add(range)?;
+            target.checked_add(range)?;
+            Some(Entry {
+                target,
+                source,
+                range,
+            })
+        },
+    )(input)
+}
+
+fn initial_seeds(input: &str) -> IResult<&str, Vec<Id>> {
+    delimited(
+        tag("seeds: "),
+        separated_list1(tag(" "), integer),
+        tag("\n"),
+    )(input)
+}
+
+fn mapping(input: &str) -> IResult<&str, Mapping> {
+    preceded(
+        pair(many1(none_of(":")), tag(":\n")),
+        map(many1(terminated(entry, tag("\n"))), Mapping::new),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<(Vec<Id>, Vec<Mapping>), ParseError> {
+    finish(
+        input,
+        separated_pair(
+            initial_seeds,
+            tag("\n"),
+            separated_list1(tag("\n"), mapping),
+        )(input),
+    )
+}
+
+impl Entry {
+    fn compare(&self, source: Id) -> Ordering {
+        if self.source + self.range <= source {
+            return Ordering::Less;
+        }
+        if self.source > source {
+            return Ordering::Greater;
+        }
+        Ordering::Equal
+    }
+
+    fn sources(&self) -> Interval<Id> {
+        Interval::new(self.source, self.source + self.range)
+    }
+}
+
+impl Mapping {
+    fn apply(&self, source: Id) -> Id {
+        match self.entries.binary_search_by(|e| e.compare(source)) {
+            Ok(index) => {
+                let entry = &self.entries[index];
+                checked!(entry.target + (source - entry.source))
+            }
+            Err(_) => source,
+        }
+    }
+}
+
+pub fn solve_part1(seeds: &[Id], mappings: &[Mapping]) -> Id {
+    seeds
+        .iter()
+        .map(|&s| mappings.iter().fold(s, |acc, m| m.apply(acc)))
+        .min()
+        .unwrap()
+}
+
+/// The seeds read as pairs of a start and a length, or `None` if a range runs past the largest
+/// id.
+pub fn into_intervals(seed_data: &[Id]) -> Option<IntervalSet<Id>> {
+    seed_data
+        .chunks_exact(2)
+        .map(|data| (data[0], data[1]))
+        .map(|(start, len)| Some(Interval::new(start, start.checked_add(len)?)))
+        .collect()
+}
+
+impl Mapping {
+    fn apply_all(&self, ids: &IntervalSet<Id>) -> IntervalSet<Id> {
+        let mut unmapped = ids.clone();
+        let mut mapped = Vec::new();
+        for entry in &self.entries {
+            let sources = IntervalSet::from(entry.sources());
+            mapped.extend(unmapped.intersection(&sources).iter().map(|i| {
+                Interval::new(
+                    checked!(entry.target + (i.start - entry.source)),
+                    checked!(entry.target + (i.end - entry.source)),
+                )
+            }));
+            unmapped = unmapped.difference(&sources);
+        }
+        mapped.into_iter().chain(unmapped.iter()).collect()
+    }
+}
+
+pub fn solve_part2(seeds: IntervalSet<Id>, mappings: &[Mapping]) -> Option<Id> {
+    mappings
+        .iter()
+        .fold(seeds, |ids, m| m.apply_all(&ids))
+        .min()
+}
+
+pub(crate) struct Explorer {
+    seeds: Vec<Id>,
+    mappings: Vec<Mapping>,
+}
+
+impl Explorer {
+    pub(crate) fn new(input: &str) -> Result<Self, ParseError> {
+        let (seeds, mappings) = parse_input(input)?;
+        Ok(Self { seeds, mappings })
+    }
+}
+
+impl crate::repl::Explore for Explorer {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("seeds", "list the initial seeds"),
+            ("map <seed>", "follow a seed through every mapping"),
+        ]
+    }
+
+    fn query(&self, command: &str, args: &str) -> Result<String, String> {
+        match command {
+            "seeds" => Ok(self.seeds.iter().join(" ")),
+            "map" => {
+                let [seed] = crate::repl::arguments(args)?;
+                Ok(self
+                    .mappings
+                    .iter()
+                    .scan(seed, |id, m| {
+                        *id = m.apply(*id);
+                        Some(*id)
+                    })
+                    .fold(seed.to_string(), |acc, id| format!("{acc} -> {id}")))
+            }
+            _ => Err(format!("unknown query `{command}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn test_solve() {
+        let (seeds, mappings) = parse_input(EXAMPLE).unwrap();
+        assert_eq!(solve_part1(&seeds, &mappings), 35);
+        let intervals = into_intervals(&seeds).unwrap();
+        assert_eq!(solve_part2(intervals, &mappings), Some(46));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert!(parse_input("seeds: 1\n\nmap:\n18446744073709551615 0 5\n").is_err());
+        assert!(parse_input("seeds: 1\n\nmap:\n0 18446744073709551615 5\n").is_err());
+        let (seeds, mappings) =
+            parse_input("seeds: 18446744073709551615 5\n\nmap:\n0 0 5\n").unwrap();
+        assert_eq!(solve_part1(&seeds, &mappings), 5);
+        assert!(into_intervals(&seeds).is_none());
+    }
+}
@@ -0,0 +1,170 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, multispace0, multispace1, one_of},
+    combinator::{map_res, recognize},
+    multi::{many0, many1, separated_list1},
+    sequence::{preceded, separated_pair, terminated},
+    IResult,
+};
+
+use crate::{math, parse::finish, ParseError};
+
+#[derive(Debug, Default)]
+pub struct Race {
+    time: u64,
+    distance: u64,
+}
+
+fn integer(input: &str) -> IResult<&str, u64> {
+    map_res(
+        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Race>, ParseError> {
+    let (times, distances) = finish(
+        input,
+        separated_pair(
+            preceded(
+                tag("Time:"),
+                preceded(multispace0, separated_list1(multispace1, integer)),
+            ),
+            multispace1,
+            preceded(
+                tag("Distance:"),
+                preceded(multispace0, separated_list1(multispace1, integer)),
+            ),
+        )(input),
+    )?;
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
+}
+
+struct Outcome {
+    race_time: u64,
+    hold_time: u64,
+}
+
+impl Outcome {
+    /// The distance travelled, in a `u128` which holds it whatever the times.
+    fn distance(&self) -> u128 {
+        if self.hold_time == 0 || self.hold_time >= self.race_time {
+            return 0;
+        }
+        let race_time = u128::from(self.race_time);
+        let hold_time = u128::from(self.hold_time);
+        (race_time - hold_time) * hold_time
+    }
+}
+
+/// Counts the hold times `h` with `h * (time - h) > distance`, which lie strictly between the
+/// roots of the quadratic and symmetrically around `time / 2`.
+fn record_count(race: &Race) -> usize {
+    let beats = |hold_time| {
+        Outcome {
+            race_time: race.time,
+            hold_time,
+        }
+        .distance()
+            > race.distance.into()
+    };
+    // The square of a `u64` time, like four times a `u64` distance, fits in a `u128`.
+    let time = u128::from(race.time);
+    let distance = u128::from(race.distance);
+    let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+        return 0;
+    };
+    // The integer square root lands the estimate of the shortest winning hold within a step.
+    let mut shortest = (race.time - math::isqrt(discriminant)) / 2;
+    while shortest > 0 && beats(shortest - 1) {
+        shortest -= 1;
+    }
+    while shortest <= race.time / 2 && !beats(shortest) {
+        shortest += 1;
+    }
+    if shortest > race.time / 2 {
+        return 0;
+    }
+    (race.time - 2 * shortest + 1) as usize
+}
+
+/// The product of the numbers of ways to beat each record, or `None` if it does not fit in a
+/// `usize`.
+pub fn solve_part1(races: &[Race]) -> Option<usize> {
+    races
+        .iter()
+        .try_fold(1usize, |acc, race| acc.checked_mul(record_count(race)))
+}
+
+/// The digits of `head` followed by those of `tail`, or `None` if they do not fit in a `u64`.
+fn join(head: u64, tail: u64) -> Option<u64> {
+    let mut offset: u64 = 10;
+    let mut rem = tail / 10;
+    while rem > 0 {
+        offset = offset.checked_mul(10)?;
+        rem /= 10;
+    }
+    offset.checked_mul(head)?.checked_add(tail)
+}
+
+impl Race {
+    /// The race written by the digits of all of `races`, or `None` if its numbers do not fit
+    /// in a `u64`.
+    fn collapse(races: &[Self]) -> Option<Self> {
+        races.iter().try_fold(Race::default(), |acc, x| {
+            Some(Race {
+                time: join(acc.time, x.time)?,
+                distance: join(acc.distance, x.distance)?,
+            })
+        })
+    }
+}
+
+/// The number of ways to beat the record of the single race the input describes, or `None` if
+/// its numbers do not fit in a `u64`.
+pub fn solve_part2(races: &[Race]) -> Option<usize> {
+    Race::collapse(races).map(|race| record_count(&race))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record_count() {
+        for (time, distance, expected) in
+            [(7, 9, 4), (15, 40, 8), (30, 200, 9), (0, 0, 0), (4, 4, 0)]
+        {
+            assert_eq!(record_count(&Race { time, distance }), expected);
+        }
+        let race = Race {
+            time: 71530,
+            distance: 940200,
+        };
+        assert_eq!(record_count(&race), 71503);
+        let race = Race {
+            time: 1 << 32,
+            distance: 1 << 62,
+        };
+        assert_eq!(record_count(&race), 0);
+        let race = Race {
+            time: u64::MAX,
+            distance: 0,
+        };
+        assert_eq!(record_count(&race), (u64::MAX - 1) as usize);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let races = parse_input("Time: 7 15 30\nDistance: 9 40 200").unwrap();
+        assert_eq!(solve_part1(&races), Some(4 * 8 * 9));
+        assert_eq!(solve_part2(&races), Some(71503));
+        let races = parse_input("Time: 9999999999 9999999999\nDistance: 1 1").unwrap();
+        assert_eq!(solve_part1(&races), None);
+        assert_eq!(solve_part2(&races), None);
+    }
+}
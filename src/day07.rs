@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+use counter::Counter;
+use nom::{
+    character::complete::{char, multispace1, one_of, space1},
+    combinator::{map, map_res, recognize},
+    multi::{many0, many1, many_m_n, separated_list1},
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+
+use crate::{parse::finish, ParseError};
+
+type Money = u32;
+
+#[derive(Debug, Clone, Copy)]
+struct Cards<'a>(&'a str);
+
+const RANKS: &str = "23456789TJQKA";
+const ALT_RANKS: &str = "J23456789TQKA";
+
+#[derive(Debug, Clone)]
+pub struct Hand<'a> {
+    cards: Cards<'a>,
+    bet: Money,
+}
+
+fn integer<I: FromStr>(input: &str) -> IResult<&str, I> {
+    map_res(
+        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Hand<'_>>, ParseError> {
+    finish(
+        input,
+        separated_list1(
+            multispace1,
+            map(
+                separated_pair(
+                    recognize(many_m_n(5, 5, one_of(RANKS))),
+                    space1,
+                    integer::<Money>,
+                ),
+                |(cards, bet)| Hand {
+                    cards: Cards(cards),
+                    bet,
+                },
+            ),
+        )(input),
+    )
+}
+
+fn count(cards: &Cards) -> Counter<char> {
+    cards.0.chars().collect()
+}
+
+fn ranks(cards: &Cards) -> Vec<usize> {
+    cards
+        .0
+        .chars()
+        .map(|c| RANKS.chars().position(|a| a == c).unwrap())
+        .collect()
+}
+
+fn alt_ranks(cards: &Cards) -> Vec<usize> {
+    cards
+        .0
+        .chars()
+        .map(|c| ALT_RANKS.chars().position(|a| a == c).unwrap())
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Strength {
+    counts: Vec<usize>,
+    ranks: Vec<usize>,
+}
+
+impl Hand<'_> {
+    fn strength(&self) -> Strength {
+        Strength {
+            counts: count(&self.cards)
+                .most_common()
+                .into_iter()
+                .map(|(_, c)| c)
+                .collect(),
+            ranks: ranks(&self.cards),
+        }
+    }
+
+    fn alt_strength(&self) -> Strength {
+        let counts = count(&self.cards);
+        let jokers = counts.get(&'J').copied().unwrap_or_default();
+        let mut counts: Vec<_> = counts
+            .most_common()
+            .into_iter()
+            .filter_map(|(c, n)| match c {
+                'J' => None,
+                _ => Some(n),
+            })
+            .collect();
+        if counts.is_empty() {
+            counts.push(0);
+        }
+        counts[0] += jokers;
+        Strength {
+            counts,
+            ranks: alt_ranks(&self.cards),
+        }
+    }
+}
+
+/// The total winnings with hands ranked by `key`, or `None` if they do not fit.
+fn solve_with_key<'a>(hands: &[Hand<'a>], key: fn(&Hand<'a>) -> Strength) -> Option<u64> {
+    let sorted_hands = {
+        let mut hands = hands.to_vec();
+        hands.sort_unstable_by_key(key);
+        hands
+    };
+    (1..).zip(sorted_hands).try_fold(0u64, |acc, (rank, h)| {
+        acc.checked_add(u64::checked_mul(rank, h.bet.into())?)
+    })
+}
+
+pub fn solve_part1(hands: &[Hand]) -> Option<u64> {
+    solve_with_key(hands, Hand::strength)
+}
+
+pub fn solve_part2(hands: &[Hand]) -> Option<u64> {
+    solve_with_key(hands, Hand::alt_strength)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overflow() {
+        let hands = [
+            Hand {
+                cards: Cards("23456"),
+                bet: Money::MAX,
+            },
+            Hand {
+                cards: Cards("AAAAA"),
+                bet: Money::MAX,
+            },
+        ];
+        assert_eq!(solve_part1(&hands), Some(3 * u64::from(Money::MAX)));
+        let many = vec![hands[0].clone(); 1 << 17];
+        let unranked = |_: &Hand| Strength {
+            counts: vec![],
+            ranks: vec![],
+        };
+        assert_eq!(solve_with_key(&many, unranked), None);
+    }
+}
@@ -0,0 +1,188 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{anychar, multispace1, one_of},
+    combinator::{map, recognize},
+    multi::{many1, many_m_n, separated_list1},
+    sequence::{delimited, separated_pair},
+};
+use std::collections::HashMap;
+
+use crate::{
+    budget::{Budget, Timeout},
+    math,
+    parse::finish,
+    stats::Stat,
+    ParseError,
+};
+
+type Direction = char;
+type Node<'a> = &'a str;
+type Transitions<'a> = HashMap<Node<'a>, (Node<'a>, Node<'a>)>;
+
+/// Parses the directions and the network, whose nodes must all lead to nodes of the network.
+pub fn parse_input(input: &str) -> Result<(Vec<Direction>, Transitions<'_>), ParseError> {
+    let node = |s| recognize(many_m_n(3, 3, anychar::<&str, nom::error::Error<&str>>))(s);
+    let result = separated_pair(
+        many1(one_of("LR")),
+        multispace1,
+        map(
+            separated_list1(
+                tag("\n"),
+                separated_pair(
+                    node,
+                    tag(" = "),
+                    delimited(tag("("), separated_pair(node, tag(", "), node), tag(")")),
+                ),
+            ),
+            |list| list.into_iter().collect(),
+        ),
+    )(input);
+    let (directions, transitions): (_, Transitions) = finish(input, result)?;
+    if let Some(dangling) = transitions
+        .values()
+        .flat_map(|&(left, right)| [left, right])
+        .find(|node| !transitions.contains_key(node))
+    {
+        return Err(ParseError::at(input, dangling));
+    }
+    Ok((directions, transitions))
+}
+
+fn step<'a>(transitions: &Transitions<'a>, node: Node<'a>, dir: Direction) -> Node<'a> {
+    let candidates = transitions[node];
+    match dir {
+        'L' => candidates.0,
+        'R' => candidates.1,
+        _ => panic!(),
+    }
+}
+
+/// Number of steps from `AAA` to `ZZZ`, or `None` if the network has no node `AAA`.
+pub fn solve_part1(
+    directions: &[Direction],
+    transitions: &Transitions,
+    budget: &mut Budget,
+) -> Result<Option<usize>, Timeout> {
+    if !transitions.contains_key("AAA") {
+        return Ok(None);
+    }
+    let mut state = "AAA";
+    for (steps, &dir) in directions.iter().cycle().enumerate() {
+        if state == "ZZZ" {
+            return Ok(Some(steps));
+        }
+        budget.spend(|| format!("at node {state} after {steps} steps from AAA"))?;
+        state = step(transitions, state, dir);
+    }
+    unreachable!("the directions are never empty")
+}
+
+/// The first two numbers of steps after which the ghost leaving `start` stands on a node
+/// ending with `Z`.
+fn arrivals(
+    directions: &[Direction],
+    transitions: &Transitions,
+    start: Node,
+    budget: &mut Budget,
+) -> Result<[u64; 2], Timeout> {
+    let mut state = start;
+    let mut first = None;
+    for (steps, &dir) in (0..).zip(directions.iter().cycle()) {
+        if state.ends_with('Z') {
+            match first {
+                Some(first) => return Ok([first, steps]),
+                None => first = Some(steps),
+            }
+        }
+        budget.spend(|| format!("at node {state} after {steps} steps from {start}"))?;
+        state = step(transitions, state, dir);
+    }
+    unreachable!("the directions are never empty")
+}
+
+/// Number of steps after which every ghost stands on a node ending with `Z`, or `None` if that
+/// count does not fit in a `u64`.
+///
+/// Each ghost is assumed to reach such nodes periodically from its first arrival on.
+pub fn solve_part2(
+    directions: &[Direction],
+    transitions: &Transitions,
+    budget: &mut Budget,
+) -> Result<Option<u64>, Timeout> {
+    let periods = transitions
+        .keys()
+        .filter(|node| node.ends_with('A'))
+        .map(|&start| arrivals(directions, transitions, start, budget))
+        .collect::<Result<Vec<_>, _>>()?;
+    let first = periods.iter().map(|x| x[0]).max().unwrap_or_default();
+    let Some(solutions) = math::crt(periods.iter().map(|x| (x[0], x[1] - x[0]))) else {
+        return Ok(None);
+    };
+    Ok(math::least_at_or_above(solutions, first))
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let (directions, transitions) = parse_input(input)?;
+    let ends_with = |c| transitions.keys().filter(|n| n.ends_with(c)).count();
+    Ok(vec![
+        ("instructions", directions.len().to_string()),
+        ("nodes", transitions.len().to_string()),
+        ("edges", (2 * transitions.len()).to_string()),
+        ("start nodes", ends_with('A').to_string()),
+        ("end nodes", ends_with('Z').to_string()),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve_part1() {
+        let (directions, transitions) =
+            parse_input("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(
+            solve_part1(&directions, &transitions, &mut Budget::unlimited()),
+            Ok(Some(6))
+        );
+        let (directions, transitions) =
+            parse_input("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        let mut budget = Budget::unlimited().with_steps(10);
+        let timeout = solve_part1(&directions, &transitions, &mut budget).unwrap_err();
+        assert_eq!(timeout.progress, "at node AAA after 10 steps from AAA");
+        let (directions, transitions) = parse_input("L\n\nBBB = (BBB, BBB)").unwrap();
+        assert_eq!(
+            solve_part1(&directions, &transitions, &mut Budget::unlimited()),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_dangling_nodes() {
+        let input = "R \0\x04\r = (`RR, RRR)";
+        assert_eq!(parse_input(input), Err(ParseError { offset: 9 }));
+        assert_eq!(
+            parse_input("L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)"),
+            Err(ParseError { offset: 10 })
+        );
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        let (directions, transitions) = parse_input(input).unwrap();
+        assert_eq!(
+            solve_part2(&directions, &transitions, &mut Budget::unlimited()),
+            Ok(Some(6))
+        );
+    }
+}
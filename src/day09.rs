@@ -0,0 +1,82 @@
+use crate::ParseError;
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|token| token.parse().map_err(|_| ParseError::at(input, token)))
+                .collect()
+        })
+        .collect()
+}
+
+/// The differences between consecutive values, or `None` if one does not fit in an `i128`.
+fn derive(values: &[i128]) -> Option<Vec<i128>> {
+    values.windows(2).map(|w| w[1].checked_sub(w[0])).collect()
+}
+
+/// Extends `values` by one on each side, as the polynomial of least degree through them, or
+/// returns `None` if an extension does not fit in an `i64`.
+///
+/// The table of differences is built in `i128`, where the rows of a few dozen values cannot
+/// overflow, and gives up on longer rows that do.
+fn extrapolate(values: &[i64]) -> Option<(i64, i64)> {
+    let mut current: Vec<i128> = values.iter().map(|&v| v.into()).collect();
+    let mut extrema = vec![];
+    while current.iter().any(|&x| x != 0) {
+        extrema.push((current[0], current[current.len() - 1]));
+        current = derive(&current)?;
+    }
+    let (head, tail) = extrema
+        .into_iter()
+        .rev()
+        .try_fold((0i128, 0i128), |(acc_h, acc_t), (h, t)| {
+            Some((h.checked_sub(acc_h)?, acc_t.checked_add(t)?))
+        })?;
+    Some((head.try_into().ok()?, tail.try_into().ok()?))
+}
+
+/// The sums of the extensions of every sequence before and after it, or `None` if one of them
+/// does not fit in an `i64`.
+pub fn solve(values: &[Vec<i64>]) -> Option<(i64, i64)> {
+    let (head, tail) = values.iter().try_fold((0i64, 0i64), |(acc_h, acc_t), v| {
+        let (h, t) = extrapolate(v)?;
+        Some((acc_h.checked_add(h)?, acc_t.checked_add(t)?))
+    })?;
+    Some((head, tail))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15]), Some((-3, 18)));
+        assert_eq!(extrapolate(&[1, 3, 6, 10, 15, 21]), Some((0, 28)));
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45]), Some((5, 68)));
+    }
+
+    #[test]
+    fn test_extrapolate_single_value() {
+        assert_eq!(extrapolate(&[5]), Some((5, 5)));
+        assert_eq!(extrapolate(&[]), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_extrapolate_long_sequences() {
+        assert_eq!(extrapolate(&[1; 70]), Some((1, 1)));
+        let values: Vec<i64> = (0..100).map(|i| i * i).collect();
+        assert_eq!(extrapolate(&values), Some((1, 100 * 100)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let values = parse_input("7557553247515755322\x0c2").unwrap();
+        assert_eq!(solve(&values), None);
+        assert_eq!(extrapolate(&[i64::MAX, 0, i64::MAX]), None);
+        let alternating: Vec<i64> = (0..80).map(|i| if i % 2 == 0 { 1 } else { -1 }).collect();
+        assert_eq!(extrapolate(&alternating), None);
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashSet;
+
+use crate::{
+    geometry::{Dir4, Point2},
+    report::Rendering,
+    stats::Stat,
+    ParseError,
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Tile {
+    Ground,
+    NorthSouth,
+    EastWest,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+}
+
+pub type Loc = Point2<i64>;
+
+impl Tile {
+    fn symbol(&self) -> char {
+        match *self {
+            Tile::Ground => '.',
+            Tile::NorthSouth => '│',
+            Tile::EastWest => '─',
+            Tile::NorthEast => '└',
+            Tile::NorthWest => '┘',
+            Tile::SouthWest => '┐',
+            Tile::SouthEast => '┌',
+        }
+    }
+
+    fn directions(&self) -> Vec<Dir4> {
+        use Dir4::*;
+        use Tile::*;
+        match *self {
+            Ground => vec![],
+            NorthSouth => vec![North, South],
+            EastWest => vec![East, West],
+            NorthEast => vec![North, East],
+            NorthWest => vec![North, West],
+            SouthWest => vec![South, West],
+            SouthEast => vec![East, South],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Grid {
+    tiles: Vec<Vec<Tile>>,
+}
+
+impl Grid {
+    fn new(tiles: Vec<Vec<Tile>>, start: Loc) -> Option<Self> {
+        let mut res = Self { tiles };
+        let directions: Vec<_> = Dir4::ALL
+            .into_iter()
+            .filter(|d| {
+                res.tile(start.step(*d))
+                    .is_some_and(|t| t.directions().contains(&d.opposite()))
+            })
+            .collect();
+        use Tile::*;
+        let tile = [
+            NorthSouth, EastWest, NorthEast, NorthWest, SouthWest, SouthEast,
+        ]
+        .into_iter()
+        .find(|t| t.directions() == directions)?;
+        *res.tile_mut(start)? = tile;
+        Some(res)
+    }
+
+    fn tile(&self, loc: Loc) -> Option<&Tile> {
+        let row = usize::try_from(loc.x).ok()?;
+        let col = usize::try_from(loc.y).ok()?;
+        self.tiles.get(row)?.get(col)
+    }
+
+    fn tile_mut(&mut self, loc: Loc) -> Option<&mut Tile> {
+        let row = usize::try_from(loc.x).ok()?;
+        let col = usize::try_from(loc.y).ok()?;
+        self.tiles.get_mut(row)?.get_mut(col)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<(Grid, Loc), ParseError> {
+    use Tile::*;
+    let mut start = None;
+    let tiles = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.char_indices()
+                .map(|(j, c)| match c {
+                    '.' => Ok(Ground),
+                    '|' => Ok(NorthSouth),
+                    '-' => Ok(EastWest),
+                    'L' => Ok(NorthEast),
+                    'J' => Ok(NorthWest),
+                    '7' => Ok(SouthWest),
+                    'F' => Ok(SouthEast),
+                    'S' => {
+                        start = Some((Point2::new(i as i64, j as i64), &line[j..]));
+                        Ok(Ground)
+                    }
+                    _ => Err(ParseError::at(input, &line[j..])),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    let (start, start_position) = start.ok_or(ParseError {
+        offset: input.len(),
+    })?;
+    let grid = Grid::new(tiles, start).ok_or_else(|| ParseError::at(input, start_position))?;
+    Ok((grid, start))
+}
+
+/// Follows the pipe leaving `start`, or returns `None` if it does not loop back to it.
+pub fn find_circuit(grid: &Grid, start: Loc) -> Option<HashSet<Loc>> {
+    let mut circuit = HashSet::from([start]);
+    let mut dir = grid.tile(start)?.directions()[0];
+    let mut loc = start.step(dir);
+    while loc != start {
+        let directions = grid.tile(loc)?.directions();
+        if !directions.contains(&dir.opposite()) {
+            return None;
+        }
+        dir = directions.into_iter().find(|&d| d != dir.opposite())?;
+        circuit.insert(loc);
+        loc = loc.step(dir);
+    }
+    Some(circuit)
+}
+
+pub fn solve_part1(circuit: &HashSet<Loc>) -> usize {
+    circuit.len() / 2
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Out,
+    In,
+    InWall,
+    InIfNorth,
+    InIfSouth,
+}
+
+impl Status {
+    fn next(&self, tile: Tile) -> Self {
+        use Status::*;
+        match *self {
+            Out => match tile {
+                Tile::Ground => panic!(),
+                Tile::NorthSouth => InWall,
+                Tile::EastWest => panic!(),
+                Tile::NorthEast => InIfSouth,
+                Tile::NorthWest => panic!(),
+                Tile::SouthWest => panic!(),
+                Tile::SouthEast => InIfNorth,
+            },
+            In | InWall => match tile {
+                Tile::Ground => panic!(),
+                Tile::NorthSouth => Out,
+                Tile::EastWest => panic!(),
+                Tile::NorthEast => InIfNorth,
+                Tile::NorthWest => panic!(),
+                Tile::SouthWest => panic!(),
+                Tile::SouthEast => InIfSouth,
+            },
+            InIfNorth => match tile {
+                Tile::Ground => panic!(),
+                Tile::NorthSouth => panic!(),
+                Tile::EastWest => *self,
+                Tile::NorthEast => panic!(),
+                Tile::NorthWest => InWall,
+                Tile::SouthWest => Out,
+                Tile::SouthEast => panic!(),
+            },
+            InIfSouth => match tile {
+                Tile::Ground => panic!(),
+                Tile::NorthSouth => panic!(),
+                Tile::EastWest => *self,
+                Tile::NorthEast => panic!(),
+                Tile::NorthWest => Out,
+                Tile::SouthWest => InWall,
+                Tile::SouthEast => panic!(),
+            },
+        }
+    }
+}
+
+pub fn solve_part2(grid: &Grid, circuit: &HashSet<Loc>) -> usize {
+    grid.tiles
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(j, tile)| (Point2::new(i as i64, j as i64), tile))
+                .scan(Status::Out, |status, (loc, tile)| {
+                    *status = if circuit.contains(&loc) {
+                        status.next(*tile)
+                    } else if *status == Status::InWall {
+                        Status::In
+                    } else {
+                        *status
+                    };
+                    Some(*status)
+                })
+                .filter(|&status| status == Status::In)
+                .count()
+        })
+        .sum()
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let (grid, start) = parse_input(input)?;
+    let circuit = find_circuit(&grid, start);
+    Ok(vec![
+        ("rows", grid.tiles.len().to_string()),
+        (
+            "columns",
+            grid.tiles.first().map_or(0, Vec::len).to_string(),
+        ),
+        ("start", start.to_string()),
+        (
+            "loop length",
+            circuit.map_or("none".to_string(), |c| c.len().to_string()),
+        ),
+    ])
+}
+
+/// Draws the pipes with box-drawing characters, highlighting the loop through the start.
+pub(crate) fn render(input: &str) -> Result<Rendering, ParseError> {
+    let (grid, start) = parse_input(input)?;
+    let circuit = find_circuit(&grid, start).unwrap_or_default();
+    Ok(grid
+        .tiles
+        .iter()
+        .zip(0..)
+        .map(|(row, i)| {
+            row.iter()
+                .zip(0..)
+                .map(|(tile, j)| (tile.symbol(), circuit.contains(&Point2::new(i, j))))
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_input_without_start() {
+        assert!(parse_input("F-7\n|.|\nL-J").is_err());
+    }
+
+    #[test]
+    fn test_find_circuit_broken_pipe() {
+        let input = ".....
+.S-7.
+.|.|.
+.L-|.
+.....";
+        let (grid, start) = parse_input(input).unwrap();
+        assert_eq!(find_circuit(&grid, start), None);
+    }
+
+    #[test]
+    fn test_stats() {
+        let input = "-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF";
+        let stats = stats(input).unwrap();
+        assert_eq!(stats[0], ("rows", "5".to_string()));
+        assert_eq!(stats[2], ("start", "(1, 1)".to_string()));
+        assert_eq!(stats[3], ("loop length", "8".to_string()));
+    }
+
+    #[test]
+    fn test_render() {
+        let rendering = render("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF").unwrap();
+        let row: String = rendering[1].iter().map(|&(c, _)| c).collect();
+        assert_eq!(row, "┐┌─┐│");
+        let highlighted: Vec<_> = rendering[1].iter().map(|&(_, h)| h).collect();
+        assert_eq!(highlighted, [false, true, true, true, false]);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let input = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+        let (grid, start) = parse_input(input).unwrap();
+        let circuit = find_circuit(&grid, start).unwrap();
+        assert_eq!(solve_part2(&grid, &circuit), 4);
+    }
+}
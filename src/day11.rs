@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+use std::iter::{repeat, successors, zip};
+use std::ops::Range;
+
+use crate::{geometry::Point2, parse::grid, ParseError};
+
+type Loc = Point2<i64>;
+
+fn inner_range(a: i64, b: i64) -> Range<i64> {
+    a.min(b) + 1..a.max(b)
+}
+
+#[derive(Debug)]
+pub struct Universe {
+    galaxies: HashSet<Loc>,
+    expanded_rows: Vec<i64>,
+    expanded_cols: Vec<i64>,
+}
+
+impl Universe {
+    fn new(space: Vec<Vec<bool>>) -> Self {
+        let galaxies = space
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(move |(j, b)| b.then_some(Loc::new(i as i64, j as i64)))
+            })
+            .collect();
+
+        let expanded_rows = space
+            .iter()
+            .enumerate()
+            .filter_map(|(i, row)| row.iter().all(|b| !b).then_some(i as i64))
+            .collect();
+        let expanded_cols = (0..space.first().map_or(0, Vec::len) as i64)
+            .filter(|j| space.iter().all(|row| !row[*j as usize]))
+            .collect();
+
+        Universe {
+            galaxies,
+            expanded_rows,
+            expanded_cols,
+        }
+    }
+
+    fn galaxy_locations(&self) -> impl Iterator<Item = Loc> + Clone + '_ {
+        self.galaxies.iter().copied()
+    }
+
+    fn galaxy_pairs(&self) -> impl Iterator<Item = (Loc, Loc)> + '_ {
+        let left_iters = self.galaxy_locations().map(repeat);
+        let right_iters = successors(Some(self.galaxy_locations().skip(1)), |it| {
+            let mut res = it.clone();
+            res.next().is_some().then_some(res)
+        });
+        zip(left_iters, right_iters).flat_map(|(r, l)| zip(l, r))
+    }
+
+    fn distance(&self, a: Loc, b: Loc, age_factor: i64) -> i64 {
+        a.manhattan_distance(b)
+            + (age_factor - 1)
+                * (inner_range(a.x, b.x)
+                    .filter(|i| self.expanded_rows.binary_search(i).is_ok())
+                    .count() as i64
+                    + inner_range(a.y, b.y)
+                        .filter(|j| self.expanded_cols.binary_search(j).is_ok())
+                        .count() as i64)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Universe, ParseError> {
+    let space = grid(input, |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    })?;
+    Ok(Universe::new(space))
+}
+
+fn solve(universe: &Universe, age_factor: i64) -> i64 {
+    universe
+        .galaxy_pairs()
+        .map(|p| universe.distance(p.0, p.1, age_factor))
+        .sum()
+}
+
+pub fn solve_part1(universe: &Universe) -> i64 {
+    solve(universe, 2)
+}
+
+pub fn solve_part2(universe: &Universe) -> i64 {
+    solve(universe, 1_000_000)
+}
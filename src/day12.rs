@@ -0,0 +1,269 @@
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{anychar, char, newline, one_of, space1},
+    combinator::{map, map_res, recognize},
+    multi::{many0, many1, separated_list1},
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+use strum::EnumIs;
+
+use crate::{hash::HashMap, parse::finish, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIs, Hash)]
+enum Condition {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+impl TryFrom<char> for Condition {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Condition::Operational),
+            '#' => Ok(Condition::Damaged),
+            '?' => Ok(Condition::Unknown),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Record {
+    conditions: Vec<Condition>,
+    checksum: Vec<u8>,
+}
+
+fn integer<I: FromStr>(input: &str) -> IResult<&str, I> {
+    map_res(
+        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+#[derive(Debug, Default)]
+pub struct Memoization(HashMap<(Vec<Condition>, Vec<u8>), Option<usize>>);
+
+impl Memoization {
+    fn get(&self, key: &(Vec<Condition>, Vec<u8>)) -> Option<Option<usize>> {
+        self.0.get(key).copied()
+    }
+
+    fn set(&mut self, key: (Vec<Condition>, Vec<u8>), value: Option<usize>) {
+        self.0.insert(key, value);
+    }
+}
+
+#[derive(Debug)]
+struct Status {
+    conditions: Vec<Condition>,
+    checksum: Vec<u8>,
+    damaged_count: usize,
+    upper_bound: usize,
+    lower_bound: usize,
+}
+
+impl Record {
+    /// The number of arrangements matching the checksum, or `None` if it does not fit.
+    fn valid_combinations(&self, memo: &mut Memoization) -> Option<usize> {
+        let damaged_count = self.checksum.iter().fold(0, |acc, x| acc + *x as usize);
+        let upper_bound = self
+            .conditions
+            .iter()
+            .filter(|c| !c.is_operational())
+            .count();
+        let lower_bound = self.conditions.iter().filter(|c| c.is_damaged()).count();
+        let status = Status {
+            conditions: self.conditions.clone(),
+            checksum: self.checksum.clone(),
+            damaged_count,
+            upper_bound,
+            lower_bound,
+        };
+        status.valid_combinations(memo)
+    }
+
+    pub fn unfold(self) -> Self {
+        Self {
+            conditions: [&self.conditions[..]; 5].join(&Condition::Unknown),
+            checksum: self.checksum.repeat(5),
+        }
+    }
+}
+
+impl Status {
+    fn valid_combinations(mut self, memo: &mut Memoization) -> Option<usize> {
+        let key = (self.conditions, self.checksum);
+        if let Some(res) = memo.get(&key) {
+            return res;
+        }
+        self.conditions = key.0.clone();
+        self.checksum = key.1.clone();
+        if self.damaged_count < self.lower_bound || self.damaged_count > self.upper_bound {
+            memo.set(key, Some(0));
+            return Some(0);
+        }
+        if self.damaged_count == 0 {
+            memo.set(key, Some(1));
+            return Some(1);
+        }
+        let tail = self.conditions.last().unwrap();
+        match tail {
+            Condition::Damaged => {
+                let checksum_tail = *self.checksum.last().unwrap() as usize;
+                let (damaged_count, unknown_count) =
+                    self.conditions.iter().rev().take(checksum_tail).fold(
+                        (0, 0),
+                        |acc, c| match c {
+                            Condition::Operational => acc,
+                            Condition::Damaged => (acc.0 + 1, acc.1),
+                            Condition::Unknown => (acc.0, acc.1 + 1),
+                        },
+                    );
+                if damaged_count + unknown_count != checksum_tail {
+                    memo.set(key, Some(0));
+                    return Some(0);
+                }
+                if checksum_tail == self.conditions.len() {
+                    memo.set(key, Some(1));
+                    return Some(1);
+                }
+                let additional_unknown = match self
+                    .conditions
+                    .get((self.conditions.len() - 1) - checksum_tail)
+                    .unwrap()
+                {
+                    Condition::Operational => 0,
+                    Condition::Damaged => {
+                        return Some(0);
+                    }
+                    Condition::Unknown => 1,
+                };
+                self.checksum.pop();
+                self.conditions
+                    .truncate(self.conditions.len() - (checksum_tail + 1));
+                let res = Self {
+                    damaged_count: self.damaged_count - checksum_tail,
+                    upper_bound: self.upper_bound - (checksum_tail + additional_unknown),
+                    lower_bound: self.lower_bound - damaged_count,
+                    ..self
+                }
+                .valid_combinations(memo);
+                memo.set(key, res);
+                res
+            }
+            Condition::Operational => {
+                self.conditions.pop();
+                let res = Self { ..self }.valid_combinations(memo);
+                memo.set(key, res);
+                res
+            }
+            Condition::Unknown => {
+                let mut conditions_damaged = self.conditions.clone();
+                *conditions_damaged.last_mut().unwrap() = Condition::Damaged;
+                let mut conditions_operational = self.conditions;
+                conditions_operational.pop();
+                let damaged = Self {
+                    conditions: conditions_damaged,
+                    lower_bound: self.lower_bound + 1,
+                    checksum: self.checksum.clone(),
+                    ..self
+                }
+                .valid_combinations(memo);
+                let operational = Self {
+                    conditions: conditions_operational,
+                    upper_bound: self.upper_bound - 1,
+                    ..self
+                }
+                .valid_combinations(memo);
+                let res = damaged.zip(operational).and_then(|(d, o)| d.checked_add(o));
+                memo.set(key, res);
+                res
+            }
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Record>, ParseError> {
+    finish(
+        input,
+        separated_list1(
+            newline,
+            map(
+                separated_pair(
+                    many1(map_res(anychar, |c| c.try_into())),
+                    space1,
+                    separated_list1(char(','), integer),
+                ),
+                |(conditions, checksum)| Record {
+                    conditions,
+                    checksum,
+                },
+            ),
+        )(input),
+    )
+}
+
+/// The total number of arrangements over all records, or `None` if it does not fit.
+pub fn solve(records: &[Record], memo: &mut Memoization) -> Option<usize> {
+    records.iter().try_fold(0usize, |acc, r| {
+        acc.checked_add(r.valid_combinations(memo)?)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_valid_combinations() {
+        use Condition::*;
+        assert_eq!(
+            Record {
+                conditions: vec![
+                    Unknown,
+                    Unknown,
+                    Unknown,
+                    Operational,
+                    Damaged,
+                    Damaged,
+                    Damaged,
+                ],
+                checksum: vec![1, 1, 3],
+            }
+            .valid_combinations(&mut Memoization::default()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_valid_combinations_unfolded() {
+        use Condition::*;
+        assert_eq!(
+            Record {
+                conditions: vec![
+                    Unknown, Damaged, Damaged, Damaged, Unknown, Unknown, Unknown, Unknown,
+                    Unknown, Unknown, Unknown, Unknown,
+                ],
+                checksum: vec![3, 2, 1],
+            }
+            .unfold()
+            .valid_combinations(&mut Memoization::default()),
+            Some(506250)
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let records = parse_input(&format!("{} 1,1,1,1,1,1,1,1,1,1", "?".repeat(40))).unwrap();
+        let mut memo = Memoization::default();
+        // Ten springs and the nine gaps between them leave 21 to spread over 11 places.
+        let expected = crate::math::binomial(31, 10).unwrap() as usize;
+        assert_eq!(solve(&records, &mut memo), Some(expected));
+        let records: Vec<_> = records.into_iter().map(|r| r.unfold()).collect();
+        assert_eq!(solve(&records, &mut memo), None);
+    }
+}
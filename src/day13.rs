@@ -0,0 +1,200 @@
+use std::iter::zip;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, multispace1, newline},
+    combinator::{map, verify},
+    multi::{many1, separated_list1},
+    IResult,
+};
+
+use crate::{parse::finish, ParseError};
+
+enum Direction {
+    Horizontal,
+    Vertical,
+}
+
+struct Split {
+    direction: Direction,
+    offset: usize,
+}
+
+impl Split {
+    fn new(direction: Direction, offset: usize) -> Self {
+        Self { direction, offset }
+    }
+}
+
+pub struct Pattern {
+    rocks: Vec<Vec<bool>>,
+}
+
+impl Pattern {
+    fn has_horizontal_reflection(&self, offset: usize) -> bool {
+        let (upper, lower) = self.rocks.split_at(offset);
+        zip(upper.iter().rev(), lower.iter()).all(|(u, l)| u == l)
+    }
+
+    fn has_vertical_reflection(&self, offset: usize) -> bool {
+        self.rocks.iter().all(|row| {
+            let (left, right) = row.split_at(offset);
+            zip(left.iter().rev(), right.iter()).all(|(u, l)| u == l)
+        })
+    }
+
+    fn has_smudgy_horizontal_reflection(&self, offset: usize) -> bool {
+        let (upper, lower) = self.rocks.split_at(offset);
+        zip(upper.iter().rev(), lower.iter())
+            .map(|(u, l)| zip(u, l).filter(|(u, l)| u != l).count())
+            .sum::<usize>()
+            == 1
+    }
+
+    fn has_smudgy_vertical_reflection(&self, offset: usize) -> bool {
+        self.rocks
+            .iter()
+            .map(|row| {
+                let (left, right) = row.split_at(offset);
+                zip(left.iter().rev(), right.iter())
+                    .filter(|(u, l)| u != l)
+                    .count()
+            })
+            .sum::<usize>()
+            == 1
+    }
+
+    fn rows(&self) -> usize {
+        self.rocks.len()
+    }
+
+    fn cols(&self) -> usize {
+        self.rocks[0].len()
+    }
+
+    fn find_reflection(&self) -> Option<Split> {
+        if let Some(i) = (1..self.rows()).find(|&i| self.has_horizontal_reflection(i)) {
+            return Some(Split::new(Direction::Horizontal, i));
+        }
+        (1..self.cols())
+            .find(|&j| self.has_vertical_reflection(j))
+            .map(|j| Split::new(Direction::Vertical, j))
+    }
+
+    fn find_smudgy_reflection(&self) -> Option<Split> {
+        if let Some(i) = (1..self.rows()).find(|&i| self.has_smudgy_horizontal_reflection(i)) {
+            return Some(Split::new(Direction::Horizontal, i));
+        }
+        (1..self.cols())
+            .find(|&j| self.has_smudgy_vertical_reflection(j))
+            .map(|j| Split::new(Direction::Vertical, j))
+    }
+}
+
+fn pattern(input: &str) -> IResult<&str, Pattern> {
+    map(
+        verify(
+            separated_list1(
+                newline,
+                many1(map(alt((char('.'), char('#'))), |c| c == '#')),
+            ),
+            |rocks: &Vec<Vec<bool>>| rocks.iter().all(|row| row.len() == rocks[0].len()),
+        ),
+        |rocks| Pattern { rocks },
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Pattern>, ParseError> {
+    finish(input, separated_list1(multispace1, pattern)(input))
+}
+
+/// Summarises the reflections of all patterns, or returns `None` if one has none.
+pub fn solve_part1(patterns: &[Pattern]) -> Option<usize> {
+    patterns
+        .iter()
+        .map(|p| {
+            p.find_reflection()
+                .map(|Split { direction, offset }| match direction {
+                    Direction::Horizontal => 100 * offset,
+                    Direction::Vertical => offset,
+                })
+        })
+        .sum()
+}
+
+/// Summarises the reflections of all patterns once smudged, or returns `None` if one has none.
+pub fn solve_part2(patterns: &[Pattern]) -> Option<usize> {
+    patterns
+        .iter()
+        .map(|p| {
+            p.find_smudgy_reflection()
+                .map(|Split { direction, offset }| match direction {
+                    Direction::Horizontal => 100 * offset,
+                    Direction::Vertical => offset,
+                })
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_input, pattern, solve_part1, solve_part2};
+
+    #[test]
+    fn test_vertical_reflection() {
+        let pattern = pattern(
+            "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.",
+        )
+        .unwrap()
+        .1;
+        assert!(pattern.has_vertical_reflection(5));
+    }
+
+    #[test]
+    fn test_horizontal_reflection() {
+        let pattern = pattern(
+            "#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#",
+        )
+        .unwrap()
+        .1;
+        assert!(pattern.has_horizontal_reflection(4));
+    }
+
+    #[test]
+    fn test_has_smudgy_horizontal_reflection() {
+        let pattern = pattern(
+            "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.",
+        )
+        .unwrap()
+        .1;
+        assert!(pattern.has_smudgy_horizontal_reflection(3));
+    }
+
+    #[test]
+    fn test_no_reflection() {
+        let patterns = parse_input(".").unwrap();
+        assert_eq!(solve_part1(&patterns), None);
+        assert_eq!(solve_part2(&patterns), None);
+        let patterns = parse_input("#.\n..").unwrap();
+        assert_eq!(solve_part1(&patterns), None);
+        assert_eq!(solve_part2(&patterns), Some(100));
+    }
+}
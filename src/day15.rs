@@ -0,0 +1,201 @@
+use crate::ParseError;
+
+fn apply_hash(string: &[u8]) -> u8 {
+    string
+        .iter()
+        .fold(0, |acc, &x| acc.wrapping_add(x).wrapping_mul(17))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Lens<'a> {
+    label: &'a [u8],
+    focal_length: u8,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Box<'a> {
+    lenses: Vec<Lens<'a>>,
+}
+
+impl<'a> Box<'a> {
+    fn local_focusing_power(&self) -> u64 {
+        self.lenses
+            .iter()
+            .enumerate()
+            .map(|(i, lens)| ((i + 1) as u64) * lens.focal_length as u64)
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+enum Command {
+    Set(u8),
+    Rm,
+}
+
+#[derive(Debug)]
+struct Instruction<'a> {
+    label: &'a [u8],
+    command: Command,
+}
+
+impl<'a> Instruction<'a> {
+    fn decode(instruction: &'a [u8]) -> Option<Self> {
+        if let Some(label) = instruction.strip_suffix(b"-") {
+            return Some(Self {
+                label,
+                command: Command::Rm,
+            });
+        }
+        let (&focal_length, head) = instruction.split_last()?;
+        let label = head.strip_suffix(b"=")?;
+        focal_length.is_ascii_digit().then(|| Self {
+            label,
+            command: Command::Set(focal_length - b'0'),
+        })
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<&[u8]>, ParseError> {
+    input
+        .trim()
+        .split(',')
+        .map(|s| {
+            Instruction::decode(s.as_bytes())
+                .map(|_| s.as_bytes())
+                .ok_or_else(|| ParseError::at(input, s))
+        })
+        .collect()
+}
+
+pub fn solve_part1(data: &[&[u8]]) -> u64 {
+    data.iter().map(|&s| apply_hash(s) as u64).sum()
+}
+
+fn arrange<'a>(instructions: &[&'a [u8]]) -> Vec<Box<'a>> {
+    let mut boxes: Vec<Box> = vec![Default::default(); 256];
+    for instruction in instructions.iter().filter_map(|i| Instruction::decode(i)) {
+        let Instruction { label, command } = instruction;
+        let box_id = apply_hash(label) as usize;
+        let lenses = &mut boxes.get_mut(box_id).unwrap().lenses;
+        let pos = lenses.iter().position(|l| l.label == label);
+        match command {
+            Command::Set(focal_length) => {
+                let new_lens = Lens {
+                    label,
+                    focal_length,
+                };
+                if let Some(idx) = pos {
+                    lenses[idx] = new_lens;
+                } else {
+                    lenses.push(new_lens);
+                }
+            }
+            Command::Rm => {
+                if let Some(idx) = pos {
+                    lenses.remove(idx);
+                }
+            }
+        }
+    }
+    boxes
+}
+
+pub fn solve_part2(instructions: &[&[u8]]) -> u64 {
+    arrange(instructions)
+        .into_iter()
+        .enumerate()
+        .map(|(i, b)| (i as u64 + 1) * b.local_focusing_power())
+        .sum()
+}
+
+pub(crate) struct Explorer<'a> {
+    instructions: Vec<&'a [u8]>,
+}
+
+impl<'a> Explorer<'a> {
+    pub(crate) fn new(input: &'a str) -> Result<Self, ParseError> {
+        Ok(Self {
+            instructions: parse_input(input)?,
+        })
+    }
+}
+
+impl crate::repl::Explore for Explorer<'_> {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("hash <string>", "apply the HASH algorithm"),
+            ("boxes <n>", "show the boxes after the first n steps"),
+        ]
+    }
+
+    fn query(&self, command: &str, args: &str) -> Result<String, String> {
+        match command {
+            "hash" => Ok(apply_hash(args.as_bytes()).to_string()),
+            "boxes" => {
+                let [n] = crate::repl::arguments::<usize, 1>(args)?;
+                let boxes = arrange(&self.instructions[..n.min(self.instructions.len())]);
+                Ok(boxes
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, b)| !b.lenses.is_empty())
+                    .map(|(i, b)| {
+                        let lenses = b.lenses.iter().map(|l| {
+                            format!("[{} {}]", String::from_utf8_lossy(l.label), l.focal_length)
+                        });
+                        format!("Box {i}: {}", lenses.collect::<Vec<_>>().join(" "))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+            _ => Err(format!("unknown query `{command}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{apply_hash, parse_input, solve_part1, solve_part2, Explorer};
+    use crate::repl::Explore;
+
+    #[test]
+    fn test_apply_hash() {
+        assert_eq!(apply_hash(b"HASH"), 52);
+        assert_eq!(apply_hash(b"rn=1"), 30);
+        assert_eq!(apply_hash(b"ot=7"), 231);
+    }
+
+    #[test]
+    fn test_solve_part1() {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+        let data = parse_input(input).unwrap();
+        assert_eq!(solve_part1(&data), 1320);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+        let data = parse_input(input).unwrap();
+        assert_eq!(solve_part2(&data), 145);
+    }
+
+    #[test]
+    fn test_explorer_boxes() {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+        let explorer = Explorer::new(input).unwrap();
+        assert_eq!(
+            explorer.query("boxes", "5").unwrap(),
+            "Box 0: [rn 1] [cm 2]"
+        );
+        assert_eq!(
+            explorer.query("boxes", "11").unwrap(),
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]"
+        );
+    }
+
+    #[test]
+    fn test_parse_input_invalid_step() {
+        assert!(parse_input("rn=1,,cm-").is_err());
+        assert!(parse_input("rn=x").is_err());
+    }
+}
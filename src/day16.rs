@@ -0,0 +1,162 @@
+use itertools::chain;
+
+use crate::{
+    geometry::{Dir4, Point2, Rect},
+    hash::{HashMap, HashSet},
+    parse::grid,
+    ParseError,
+};
+
+#[derive(Clone, Copy)]
+enum Tile {
+    Empty,
+    Slash,
+    Backslash,
+    Dash,
+    Pipe,
+}
+
+impl Tile {
+    fn parse(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Empty),
+            '/' => Some(Self::Slash),
+            '\\' => Some(Self::Backslash),
+            '-' => Some(Self::Dash),
+            '|' => Some(Self::Pipe),
+            _ => None,
+        }
+    }
+
+    fn outgoing_rays(&self, dir: Dir4) -> Vec<Dir4> {
+        match *self {
+            Tile::Empty => vec![dir],
+            Tile::Slash => vec![match dir {
+                Dir4::North => Dir4::East,
+                Dir4::West => Dir4::South,
+                Dir4::South => Dir4::West,
+                Dir4::East => Dir4::North,
+            }],
+            Tile::Backslash => vec![match dir {
+                Dir4::North => Dir4::West,
+                Dir4::West => Dir4::North,
+                Dir4::South => Dir4::East,
+                Dir4::East => Dir4::South,
+            }],
+            Tile::Dash => match dir {
+                Dir4::North | Dir4::South => vec![Dir4::West, Dir4::East],
+                Dir4::West | Dir4::East => vec![dir],
+            },
+            Tile::Pipe => match dir {
+                Dir4::North | Dir4::South => vec![dir],
+                Dir4::West | Dir4::East => vec![Dir4::North, Dir4::South],
+            },
+        }
+    }
+}
+
+type Loc = Point2<i64>;
+
+pub struct Layout {
+    tiles: Vec<Vec<Tile>>,
+}
+
+impl Layout {
+    fn row_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    fn col_count(&self) -> usize {
+        self.tiles.first().map_or(0, Vec::len)
+    }
+
+    fn contains(&self, loc: Loc) -> bool {
+        Rect::new(
+            Point2::default(),
+            Point2::new(self.row_count() as i64 - 1, self.col_count() as i64 - 1),
+        )
+        .contains(loc)
+    }
+
+    fn tile(&self, loc: Loc) -> Option<Tile> {
+        if self.contains(loc) {
+            Some(self.tiles[loc.x as usize][loc.y as usize])
+        } else {
+            None
+        }
+    }
+
+    fn energized_tiles(&self, dir: Dir4, loc: Loc) -> usize {
+        let mut visited: HashMap<Loc, HashSet<Dir4>> = HashMap::default();
+        let mut front = vec![(loc.step(dir.opposite()), dir)];
+        while let Some((loc, dir)) = front.pop() {
+            let next_loc = loc.step(dir);
+            if let Some(next_tile) = self.tile(next_loc) {
+                if visited.entry(next_loc).or_default().insert(dir) {
+                    for next_dir in next_tile.outgoing_rays(dir) {
+                        front.push((next_loc, next_dir));
+                    }
+                }
+            }
+        }
+        visited.len()
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Layout, ParseError> {
+    Ok(Layout {
+        tiles: grid(input, Tile::parse)?,
+    })
+}
+
+pub fn solve_part1(layout: &Layout) -> usize {
+    layout.energized_tiles(Dir4::East, Point2::new(0, 0))
+}
+
+pub fn solve_part2(layout: &Layout) -> usize {
+    let vertical = (0..layout.col_count()).flat_map(move |j| {
+        [(Dir4::South, 0), (Dir4::North, layout.row_count() - 1)]
+            .into_iter()
+            .map(move |(d, i)| (d, Point2::new(i as i64, j as i64)))
+    });
+    let horizontal = (0..layout.row_count()).flat_map(move |i| {
+        [(Dir4::East, 0), (Dir4::West, layout.col_count() - 1)]
+            .into_iter()
+            .map(move |(d, j)| (d, Point2::new(i as i64, j as i64)))
+    });
+    chain(vertical, horizontal)
+        .map(|(dir, loc)| layout.energized_tiles(dir, loc))
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn test_solve_part1() {
+        assert_eq!(solve_part1(&parse_input(INPUT).unwrap()), 46);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        assert_eq!(solve_part2(&parse_input(INPUT).unwrap()), 51);
+    }
+
+    #[test]
+    fn test_parse_input_empty_row() {
+        assert!(parse_input("\n").is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::BinaryHeap;
+
+use crate::{
+    geometry::{Dir4, Point2, Rect},
+    hash::HashMap,
+    parse::grid,
+    stats::Stat,
+    ParseError,
+};
+
+#[derive(Debug)]
+pub struct City {
+    blocks: Vec<Vec<u8>>,
+}
+
+impl City {
+    fn row_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    fn col_count(&self) -> usize {
+        self.blocks.first().map_or(0, Vec::len)
+    }
+
+    fn contains(&self, loc: Loc) -> bool {
+        Rect::new(
+            Point2::default(),
+            Point2::new(self.row_count() as i16 - 1, self.col_count() as i16 - 1),
+        )
+        .contains(loc)
+    }
+
+    fn block(&self, loc: Loc) -> Option<u8> {
+        if self.contains(loc) {
+            Some(self.blocks[loc.x as usize][loc.y as usize])
+        } else {
+            None
+        }
+    }
+}
+
+type Loc = Point2<i16>;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+struct PhysicalStatus {
+    loc: Loc,
+    dir: Dir4,
+    repeats: u8,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+struct Status<const N: u8, const M: u8> {
+    physical_status: PhysicalStatus,
+    heat_loss: u16,
+    heuristic: u16,
+}
+
+impl<const N: u8, const M: u8> Status<N, M> {
+    fn neighbors<'a>(
+        &'a self,
+        city: &'a City,
+        target: Loc,
+    ) -> impl Iterator<Item = Status<N, M>> + 'a {
+        Dir4::ALL
+            .into_iter()
+            .filter(|&d| d != self.physical_status.dir.opposite())
+            .filter(|&d| self.physical_status.repeats >= N || d == self.physical_status.dir)
+            .filter(|&d| self.physical_status.repeats < M || d != self.physical_status.dir)
+            .filter_map(move |d| {
+                let next_loc = self.physical_status.loc.step(d);
+                city.block(next_loc).map(|loss| Status {
+                    physical_status: PhysicalStatus {
+                        loc: next_loc,
+                        dir: d,
+                        repeats: {
+                            if d == self.physical_status.dir {
+                                self.physical_status.repeats + 1
+                            } else {
+                                1
+                            }
+                        },
+                    },
+                    heat_loss: self.heat_loss + loss as u16,
+                    heuristic: next_loc.manhattan_distance(target) as u16,
+                })
+            })
+    }
+}
+
+impl<const N: u8, const M: u8> PartialOrd for Status<N, M> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: u8, const M: u8> Ord for Status<N, M> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (other.heat_loss + other.heuristic).cmp(&(self.heat_loss + self.heuristic))
+    }
+}
+
+fn heat_loss<const N: u8, const M: u8>(city: &City, target: Loc) -> Option<u16> {
+    let mut visited = HashMap::default();
+    let mut front = BinaryHeap::new();
+    [Dir4::East, Dir4::South]
+        .into_iter()
+        .map(|d| Status::<N, M> {
+            physical_status: PhysicalStatus {
+                loc: Point2::new(0, 0),
+                dir: d,
+                repeats: 0,
+            },
+            heat_loss: 0,
+            heuristic: Point2::new(0, 0).manhattan_distance(target) as u16,
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .for_each(|init| {
+            visited.insert(init.physical_status, 0u16);
+            front.push(init);
+        });
+    while let Some(status) = front.pop() {
+        if status.physical_status.loc == target && status.physical_status.repeats >= N {
+            return Some(status.heat_loss);
+        }
+        for neighbor in status.neighbors(city, target) {
+            let entry = visited.entry(neighbor.physical_status).or_insert(u16::MAX);
+            if status.heat_loss < *entry {
+                *entry = status.heat_loss;
+                front.push(neighbor);
+            }
+        }
+    }
+    None
+}
+
+fn solve<const N: u8, const M: u8>(city: &City) -> u16 {
+    let target = Point2::new(city.row_count() as i16 - 1, city.col_count() as i16 - 1);
+    heat_loss::<N, M>(city, target).unwrap_or_default()
+}
+
+pub fn parse_input(input: &str) -> Result<City, ParseError> {
+    Ok(City {
+        blocks: grid(input, |c| c.to_digit(10).map(|d| d as u8))?,
+    })
+}
+
+pub fn solve_part1(city: &City) -> u16 {
+    solve::<0, 3>(city)
+}
+
+pub fn solve_part2(city: &City) -> u16 {
+    solve::<4, 10>(city)
+}
+
+pub(crate) struct Explorer {
+    city: City,
+}
+
+impl Explorer {
+    pub(crate) fn new(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            city: parse_input(input)?,
+        })
+    }
+}
+
+impl crate::repl::Explore for Explorer {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            (
+                "loss <row> <col>",
+                "least heat loss of a crucible to a block",
+            ),
+            (
+                "ultra <row> <col>",
+                "least heat loss of an ultra crucible to a block",
+            ),
+        ]
+    }
+
+    fn query(&self, command: &str, args: &str) -> Result<String, String> {
+        let heat_loss = match command {
+            "loss" => heat_loss::<0, 3>,
+            "ultra" => heat_loss::<4, 10>,
+            _ => return Err(format!("unknown query `{command}`")),
+        };
+        let [row, col] = crate::repl::arguments(args)?;
+        let target = Point2::new(row, col);
+        if !self.city.contains(target) {
+            return Err(format!("block ({row}, {col}) is outside the city"));
+        }
+        Ok(heat_loss(&self.city, target).map_or("unreachable".to_string(), |l| l.to_string()))
+    }
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let city = parse_input(input)?;
+    Ok(vec![
+        ("rows", city.row_count().to_string()),
+        ("columns", city.col_count().to_string()),
+        ("start", "(0, 0)".to_string()),
+        (
+            "target",
+            format!(
+                "({}, {})",
+                city.row_count().saturating_sub(1),
+                city.col_count().saturating_sub(1)
+            ),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_input, solve_part1, solve_part2, Explorer};
+    use crate::repl::Explore;
+
+    const INPUT: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    #[test]
+    fn test_solve_part1() {
+        let city = parse_input(INPUT).unwrap();
+        assert_eq!(solve_part1(&city), 102);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let city = parse_input(INPUT).unwrap();
+        assert_eq!(solve_part2(&city), 94);
+    }
+
+    #[test]
+    fn test_solve_part2_alt() {
+        let input = "111111111111
+999999999991
+999999999991
+999999999991
+999999999991";
+        let city = parse_input(input).unwrap();
+        assert_eq!(solve_part2(&city), 71);
+    }
+
+    #[test]
+    fn test_explorer_loss() {
+        let explorer = Explorer::new(INPUT).unwrap();
+        assert_eq!(explorer.query("loss", "12 12").unwrap(), "102");
+        assert_eq!(explorer.query("ultra", "12 12").unwrap(), "94");
+        assert_eq!(explorer.query("loss", "0 1").unwrap(), "4");
+        assert!(explorer.query("loss", "13 0").is_err());
+    }
+}
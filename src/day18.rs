@@ -0,0 +1,171 @@
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::char,
+    character::complete::{anychar, newline, one_of, space1},
+    combinator::{map, map_opt, map_res, recognize},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, separated_pair, terminated},
+    IResult,
+};
+
+use crate::{geometry::Dir4, parse::finish, ParseError};
+
+fn direction(c: char) -> Option<Dir4> {
+    match c {
+        'U' => Some(Dir4::North),
+        'L' => Some(Dir4::West),
+        'D' => Some(Dir4::South),
+        'R' => Some(Dir4::East),
+        _ => None,
+    }
+}
+
+fn hex_direction(c: char) -> Option<Dir4> {
+    match c {
+        '3' => Some(Dir4::North),
+        '2' => Some(Dir4::West),
+        '1' => Some(Dir4::South),
+        '0' => Some(Dir4::East),
+        _ => None,
+    }
+}
+
+type Coord = i64;
+
+/// Wide enough for the shoelace sums of any input that fits in memory.
+type Area = i128;
+
+#[derive(Debug, Clone)]
+pub struct Move {
+    dir: Dir4,
+    length: u32,
+}
+
+/// The number of cubes the lagoon dug by `moves` holds, or `None` if the moves do not close
+/// the loop or the count does not fit in a `Coord`.
+fn volume<'a>(moves: impl Iterator<Item = &'a Move>) -> Option<Coord> {
+    let (vol, per, x, y) = moves.fold(
+        (0 as Area, 0 as Area, 0 as Area, 0 as Area),
+        |(vol, per, x, y), Move { dir, length }| {
+            let length = Area::from(*length);
+            let per = checked!(per + length);
+            let strip = checked!(x * length);
+            match *dir {
+                Dir4::North => (checked!(vol + strip), per, x, checked!(y - length)),
+                Dir4::West => (vol, per, checked!(x - length), y),
+                Dir4::South => (checked!(vol - strip), per, x, checked!(y + length)),
+                Dir4::East => (vol, per, checked!(x + length), y),
+            }
+        },
+    );
+    if (x, y) != (0, 0) {
+        return None;
+    }
+    // Twice the area and the perimeter fit in an `Area`, so this sum does too.
+    (vol.abs() + per / 2 + 1).try_into().ok()
+}
+
+fn from_hex(input: &str) -> Result<u32, std::num::ParseIntError> {
+    u32::from_str_radix(input, 16)
+}
+
+fn is_hex_digit(c: char) -> bool {
+    c.is_ascii_hexdigit()
+}
+
+fn hex_distance(input: &str) -> IResult<&str, u32> {
+    map_res(take_while_m_n(5, 5, is_hex_digit), from_hex)(input)
+}
+
+fn integer<I: FromStr>(input: &str) -> IResult<&str, I> {
+    map_res(
+        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<(Move, Move)>, ParseError> {
+    let result = separated_list1(
+        newline,
+        separated_pair(
+            map(
+                separated_pair(map_opt(anychar, direction), space1, integer::<u32>),
+                |(dir, length)| Move { dir, length },
+            ),
+            space1,
+            map(
+                delimited(
+                    tag("(#"),
+                    pair(hex_distance, map_opt(anychar, hex_direction)),
+                    char(')'),
+                ),
+                |(length, dir)| Move { dir, length },
+            ),
+        ),
+    )(input);
+    finish(input, result)
+}
+
+pub fn solve_part1(data: &[(Move, Move)]) -> Option<Coord> {
+    volume(data.iter().map(|(m, _)| m))
+}
+
+pub fn solve_part2(data: &[(Move, Move)]) -> Option<Coord> {
+    volume(data.iter().map(|(_, m)| m))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn test_solve_part1() {
+        assert_eq!(solve_part1(&parse_input(INPUT).unwrap()), Some(62));
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        assert_eq!(
+            solve_part2(&parse_input(INPUT).unwrap()),
+            Some(952408144115)
+        );
+    }
+
+    #[test]
+    fn test_open_loops() {
+        assert!(parse_input("3 9 (#243532)").is_err());
+        let data = parse_input("R 9 (#000093)\nL 9 (#000090)").unwrap();
+        assert_eq!(solve_part1(&data), Some(10));
+        assert_eq!(solve_part2(&data), None);
+    }
+
+    #[test]
+    fn test_large_lagoon() {
+        let data = parse_input(
+            "R 4294967295 (#000000)\nD 4294967295 (#000000)\nL 4294967295 (#000000)\nU 4294967295 (#000000)",
+        )
+        .unwrap();
+        assert_eq!(solve_part1(&data), None);
+        assert_eq!(solve_part2(&data), Some(1));
+        let data =
+            parse_input("R 2 (#000000)\nD 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)").unwrap();
+        assert_eq!(solve_part1(&data), Some(9));
+    }
+}
@@ -0,0 +1,461 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, anychar, char, newline, one_of},
+    combinator::{all_consuming, map, map_res, recognize},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+use strum::EnumCount;
+
+use crate::{
+    interval::{Interval, IntervalBox},
+    parse::finish,
+    stats::Stat,
+    ParseError,
+};
+
+#[derive(Debug, Clone, Copy, EnumCount)]
+enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl TryFrom<char> for Category {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'x' => Ok(Category::X),
+            'm' => Ok(Category::M),
+            'a' => Ok(Category::A),
+            's' => Ok(Category::S),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Relation {
+    Greater,
+    Less,
+}
+
+type Rating = u16;
+
+#[derive(Debug, Clone)]
+struct Condition {
+    category: Category,
+    relation: Relation,
+    threshold: Rating,
+}
+
+impl Condition {
+    fn holds_for(&self, part: &Part) -> bool {
+        let index = self.category as usize;
+        let rating = part.ratings[index];
+        match self.relation {
+            Relation::Greater => rating > self.threshold,
+            Relation::Less => rating < self.threshold,
+        }
+    }
+
+    fn subsets(&self, spec: &Specification) -> (Specification, Specification) {
+        let axis = self.category as usize;
+        match self.relation {
+            Relation::Greater => {
+                let (below, above) = spec.split_at(axis, self.threshold.saturating_add(1));
+                (above, below)
+            }
+            Relation::Less => spec.split_at(axis, self.threshold),
+        }
+    }
+}
+
+type WorkflowId<'a> = &'a str;
+
+#[derive(Debug, Clone, Copy)]
+enum Fate<'a> {
+    Accept,
+    Reject,
+    Forward(WorkflowId<'a>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Workflow<'a> {
+    default: Fate<'a>,
+    logic: Vec<(Condition, Fate<'a>)>,
+}
+
+impl<'a> Workflow<'a> {
+    fn process(&self, part: &Part) -> Fate<'a> {
+        self.logic
+            .iter()
+            .find(|(condition, _)| condition.holds_for(part))
+            .map(|(_, fate)| *fate)
+            .unwrap_or(self.default)
+    }
+
+    fn outcomes(&self, init_spec: &Specification) -> Vec<(Fate<'a>, Specification)> {
+        let mut state = *init_spec;
+        let mut res: Vec<_> = self
+            .logic
+            .iter()
+            .scan(&mut state, |spec, (condition, fate)| {
+                let (true_spec, false_spec) = condition.subsets(spec);
+                **spec = false_spec;
+                Some((*fate, true_spec))
+            })
+            .collect();
+        res.push((self.default, state));
+        res
+    }
+
+    fn fates(&self) -> impl Iterator<Item = Fate<'a>> + '_ {
+        self.logic
+            .iter()
+            .map(|(_, fate)| *fate)
+            .chain([self.default])
+    }
+}
+
+#[derive(Debug)]
+pub struct Part {
+    ratings: [Rating; Category::COUNT],
+}
+
+struct Oracle<'a> {
+    workflows: HashMap<WorkflowId<'a>, Workflow<'a>>,
+}
+
+impl<'a> Oracle<'a> {
+    fn new<'b>(workflows: impl Iterator<Item = &'b (WorkflowId<'a>, Workflow<'a>)>) -> Self
+    where
+        'a: 'b,
+    {
+        Self {
+            workflows: workflows.cloned().collect(),
+        }
+    }
+
+    /// Returns a workflow reachable from `in` that is either undefined or part of a cycle.
+    fn find_inconsistency(&self) -> Option<WorkflowId<'a>> {
+        fn visit<'a>(
+            oracle: &Oracle<'a>,
+            workflow_id: WorkflowId<'a>,
+            path: &mut Vec<WorkflowId<'a>>,
+            checked: &mut HashSet<WorkflowId<'a>>,
+        ) -> Option<WorkflowId<'a>> {
+            if checked.contains(workflow_id) {
+                return None;
+            }
+            let Some(workflow) = oracle.workflows.get(workflow_id) else {
+                return Some(workflow_id);
+            };
+            if path.contains(&workflow_id) {
+                return Some(workflow_id);
+            }
+            path.push(workflow_id);
+            for fate in workflow.fates() {
+                if let Fate::Forward(next_id) = fate {
+                    if let Some(inconsistency) = visit(oracle, next_id, path, checked) {
+                        return Some(inconsistency);
+                    }
+                }
+            }
+            path.pop();
+            checked.insert(workflow_id);
+            None
+        }
+        visit(self, "in", &mut vec![], &mut HashSet::new())
+    }
+
+    fn is_valid(&self, part: &Part) -> bool {
+        self.trace(part, |_| {})
+    }
+
+    /// Decides whether `part` is accepted, reporting every workflow it goes through to `visit`.
+    fn trace(&self, part: &Part, mut visit: impl FnMut(WorkflowId<'a>)) -> bool {
+        let mut workflow_id = "in";
+        loop {
+            visit(workflow_id);
+            let workflow = &self.workflows[workflow_id];
+            workflow_id = match workflow.process(part) {
+                Fate::Accept => return true,
+                Fate::Reject => return false,
+                Fate::Forward(next_id) => next_id,
+            };
+        }
+    }
+
+    fn valid_specifications(&self) -> Vec<Specification> {
+        let mut res = vec![];
+        let mut front = vec![("in", ALL_RATINGS)];
+        while let Some((workflow_id, spec)) = front.pop() {
+            for (fate, spec) in self
+                .workflows
+                .get(workflow_id)
+                .map(|w| w.outcomes(&spec))
+                .unwrap()
+            {
+                match fate {
+                    Fate::Accept => res.push(spec),
+                    Fate::Reject => {}
+                    Fate::Forward(next) => front.push((next, spec)),
+                }
+            }
+        }
+        res
+    }
+}
+
+type Specification = IntervalBox<Rating, { Category::COUNT }>;
+
+/// Every combination of ratings from 1 to 4000.
+const ALL_RATINGS: Specification = IntervalBox::new([Interval::new(1, 4000 + 1); Category::COUNT]);
+
+fn integer<I: FromStr>(input: &str) -> IResult<&str, I> {
+    map_res(
+        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+fn category(input: &str) -> IResult<&str, Category> {
+    map_res(anychar, Category::try_from)(input)
+}
+
+fn part(input: &str) -> IResult<&str, Part> {
+    map(
+        delimited(
+            char('{'),
+            tuple((
+                preceded(tag("x="), integer::<Rating>),
+                preceded(tag(",m="), integer::<Rating>),
+                preceded(tag(",a="), integer::<Rating>),
+                preceded(tag(",s="), integer::<Rating>),
+            )),
+            char('}'),
+        ),
+        |(x, m, a, s)| Part {
+            ratings: [x, m, a, s],
+        },
+    )(input)
+}
+
+fn fate(input: &str) -> IResult<&str, Fate<'_>> {
+    alt((
+        map(char('A'), |_| Fate::Accept),
+        map(char('R'), |_| Fate::Reject),
+        map(alpha1, Fate::Forward),
+    ))(input)
+}
+
+fn condition(input: &str) -> IResult<&str, Condition> {
+    map(
+        tuple((
+            category,
+            alt((
+                map(char('>'), |_| Relation::Greater),
+                map(char('<'), |_| Relation::Less),
+            )),
+            integer::<Rating>,
+        )),
+        |(category, relation, threshold)| Condition {
+            category,
+            relation,
+            threshold,
+        },
+    )(input)
+}
+
+type System<'a> = (Vec<(WorkflowId<'a>, Workflow<'a>)>, Vec<Part>);
+
+pub fn parse_input(input: &str) -> Result<System<'_>, ParseError> {
+    let (workflows, parts) = finish(
+        input,
+        separated_pair(
+            separated_list1(
+                newline,
+                pair(
+                    alpha1,
+                    map(
+                        delimited(
+                            char('{'),
+                            pair(
+                                many1(terminated(
+                                    separated_pair(condition, char(':'), fate),
+                                    char(','),
+                                )),
+                                fate,
+                            ),
+                            char('}'),
+                        ),
+                        |(logic, default)| Workflow { default, logic },
+                    ),
+                ),
+            ),
+            many1(newline),
+            separated_list1(newline, part),
+        )(input),
+    )?;
+    if let Some(workflow_id) = Oracle::new(workflows.iter()).find_inconsistency() {
+        return Err(ParseError::at(input, workflow_id));
+    }
+    Ok((workflows, parts))
+}
+
+pub fn solve_part1(workflows: &[(WorkflowId, Workflow)], parts: &[Part]) -> u64 {
+    let oracle = Oracle::new(workflows.iter());
+    parts
+        .iter()
+        .filter(|&part| oracle.is_valid(part))
+        .map(|part| part.ratings.iter().map(|&r| r as u64).sum::<u64>())
+        .sum()
+}
+
+pub fn solve_part2(workflows: &[(WorkflowId, Workflow)]) -> u64 {
+    let oracle = Oracle::new(workflows.iter());
+    oracle
+        .valid_specifications()
+        .into_iter()
+        .map(|spec| spec.volume())
+        .sum()
+}
+
+pub(crate) struct Explorer<'a> {
+    oracle: Oracle<'a>,
+}
+
+impl<'a> Explorer<'a> {
+    pub(crate) fn new(input: &'a str) -> Result<Self, ParseError> {
+        let (workflows, _) = parse_input(input)?;
+        Ok(Self {
+            oracle: Oracle::new(workflows.iter()),
+        })
+    }
+}
+
+impl crate::repl::Explore for Explorer<'_> {
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[(
+            "check {x=..,m=..,a=..,s=..}",
+            "run a part through the workflows",
+        )]
+    }
+
+    fn query(&self, command: &str, args: &str) -> Result<String, String> {
+        match command {
+            "check" => {
+                let part = finish(args, all_consuming(part)(args))
+                    .map_err(|e| format!("invalid part: {e}"))?;
+                let mut route = vec![];
+                let fate = if self.oracle.trace(&part, |id| route.push(id)) {
+                    "A"
+                } else {
+                    "R"
+                };
+                Ok(format!("{} -> {fate}", route.join(" -> ")))
+            }
+            _ => Err(format!("unknown query `{command}`")),
+        }
+    }
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let (workflows, parts) = parse_input(input)?;
+    let rules: usize = workflows.iter().map(|(_, w)| w.logic.len()).sum();
+    Ok(vec![
+        ("workflows", workflows.len().to_string()),
+        ("rules", rules.to_string()),
+        ("parts", parts.len().to_string()),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_input, solve_part1, solve_part2, stats, Explorer};
+    use crate::repl::Explore;
+
+    const INPUT: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    #[test]
+    fn test_solve_part1() {
+        let (workflows, parts) = parse_input(INPUT).unwrap();
+        let answer = solve_part1(&workflows, &parts);
+        assert_eq!(answer, 19114);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let (workflows, _) = parse_input(INPUT).unwrap();
+        let answer = solve_part2(&workflows);
+        assert_eq!(answer, 167409079868000);
+    }
+
+    #[test]
+    fn test_explorer_check() {
+        let explorer = Explorer::new(INPUT).unwrap();
+        assert_eq!(
+            explorer
+                .query("check", "{x=787,m=2655,a=1222,s=2876}")
+                .unwrap(),
+            "in -> qqz -> qs -> lnx -> A"
+        );
+        assert_eq!(
+            explorer
+                .query("check", "{x=1679,m=44,a=2067,s=496}")
+                .unwrap(),
+            "in -> px -> rfg -> gd -> R"
+        );
+        assert!(explorer.query("check", "{x=1679}").is_err());
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(
+            stats(INPUT).unwrap(),
+            [
+                ("workflows", "11".to_string()),
+                ("rules", "14".to_string()),
+                ("parts", "5".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_input_incomplete_part() {
+        assert!(parse_input("in{x<10:A,R}\n\n{x=1,m=2}").is_err());
+    }
+
+    #[test]
+    fn test_parse_input_inconsistent_workflows() {
+        assert!(parse_input("in{x<10:A,ab}\n\n{x=1,m=2,a=3,s=4}").is_err());
+        assert!(parse_input("in{x<10:A,ab}\nab{m>5:in,R}\n\n{x=1,m=2,a=3,s=4}").is_err());
+        assert!(parse_input("px{x<10:A,R}\n\n{x=1,m=2,a=3,s=4}").is_err());
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, newline, one_of},
+    combinator::{map, opt},
+    multi::separated_list1,
+    sequence::{pair, separated_pair},
+};
+use strum::{EnumCount, EnumIs};
+
+use crate::{
+    budget::{Budget, Timeout},
+    math,
+    parse::finish,
+    report::Check,
+    stats::Stat,
+    ParseError,
+};
+
+type ModuleId<'a> = &'a str;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIs)]
+enum ModuleKind {
+    Broadcast,
+    FlipFlop,
+    Conjunction,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Module<'a> {
+    kind: ModuleKind,
+    destinations: Vec<ModuleId<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Network<'a> {
+    modules: HashMap<ModuleId<'a>, Module<'a>>,
+}
+
+impl<'a> Network<'a> {
+    /// The conjunction that is the only source of `rx`, if there is one.
+    fn rx_conjunction(&self) -> Option<ModuleId<'a>> {
+        match self.sources("rx")[..] {
+            [source] if self.modules[source].kind.is_conjunction() => Some(source),
+            _ => None,
+        }
+    }
+
+    fn sources(&self, module_id: ModuleId) -> Vec<ModuleId<'a>> {
+        self.modules
+            .iter()
+            .filter(|&(_id, module)| module.destinations.contains(&module_id))
+            .map(|(id, _module)| *id)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumCount, EnumIs)]
+enum Energy {
+    #[default]
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum FlipFlopState {
+    #[default]
+    Off,
+    On,
+}
+
+impl FlipFlopState {
+    fn flip(self) -> (Self, Energy) {
+        match self {
+            FlipFlopState::Off => (FlipFlopState::On, Energy::High),
+            FlipFlopState::On => (FlipFlopState::Off, Energy::Low),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NetworkState<'a> {
+    flipflop_states: HashMap<ModuleId<'a>, FlipFlopState>,
+    conjunction_states: HashMap<ModuleId<'a>, HashMap<ModuleId<'a>, Energy>>,
+    probe: Option<ModuleId<'a>>,
+}
+
+impl<'a> NetworkState<'a> {
+    fn new(network: &Network<'a>, probe: Option<ModuleId<'a>>) -> Self {
+        let flipflop_states = network
+            .modules
+            .iter()
+            .filter_map(|(&id, module)| {
+                (module.kind.is_flip_flop()).then_some((id, Default::default()))
+            })
+            .collect();
+        let conjunction_states = network
+            .modules
+            .iter()
+            .filter_map(|(&id, module)| {
+                (module.kind.is_conjunction()).then_some((
+                    id,
+                    network
+                        .modules
+                        .iter()
+                        .filter_map(|(&source_id, module)| {
+                            module
+                                .destinations
+                                .contains(&id)
+                                .then_some((source_id, Default::default()))
+                        })
+                        .collect(),
+                ))
+            })
+            .collect();
+        Self {
+            flipflop_states,
+            conjunction_states,
+            probe,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NetworkActivity<'a> {
+    network: &'a Network<'a>,
+    state: NetworkState<'a>,
+    counts: [usize; Energy::COUNT],
+    probe_signal: Vec<(ModuleId<'a>, Energy)>,
+}
+
+impl<'a> NetworkActivity<'a> {
+    fn new(network: &'a Network<'a>, probe: Option<ModuleId<'a>>) -> Self {
+        Self {
+            network,
+            state: NetworkState::new(network, probe),
+            counts: Default::default(),
+            probe_signal: Default::default(),
+        }
+    }
+
+    fn pulse_count(&self, energy: Energy) -> usize {
+        self.counts[energy as usize]
+    }
+
+    fn increment_count(&mut self, energy: Energy) {
+        self.counts[energy as usize] += 1;
+    }
+
+    fn press_button(&mut self) {
+        self.probe_signal.clear();
+        let mut pulses: VecDeque<(ModuleId, ModuleId, Energy)> = VecDeque::new();
+        pulses.push_back(("button", "broadcaster", Energy::Low));
+        while let Some((source_id, target_id, energy)) = pulses.pop_front() {
+            self.increment_count(energy);
+            if self.state.probe == Some(target_id) {
+                self.probe_signal.push((source_id, energy));
+            }
+            if let Some(target_module) = self.network.modules.get(target_id) {
+                match target_module.kind {
+                    ModuleKind::Broadcast => {
+                        for id in &target_module.destinations {
+                            pulses.push_back((target_id, id, energy));
+                        }
+                    }
+                    ModuleKind::FlipFlop => match energy {
+                        Energy::Low => {
+                            let old_state = self.state.flipflop_states.get_mut(target_id).unwrap();
+                            let (new_state, new_energy) = old_state.flip();
+                            for id in &target_module.destinations {
+                                pulses.push_back((target_id, id, new_energy));
+                            }
+                            *old_state = new_state;
+                        }
+                        Energy::High => {}
+                    },
+                    ModuleKind::Conjunction => {
+                        let state = self.state.conjunction_states.get_mut(target_id).unwrap();
+                        *state.get_mut(source_id).unwrap() = energy;
+                        let new_energy = if state.values().all(|energy| energy.is_high()) {
+                            Energy::Low
+                        } else {
+                            Energy::High
+                        };
+                        for id in &target_module.destinations {
+                            pulses.push_back((target_id, id, new_energy));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Network<'_>, ParseError> {
+    let result = map(
+        separated_list1(
+            newline,
+            map(
+                separated_pair(
+                    pair(opt(one_of("&%")), alpha1),
+                    tag(" -> "),
+                    separated_list1(tag(", "), alpha1),
+                ),
+                |((sigil, id), destinations)| {
+                    let kind = match sigil {
+                        Some(c) => match c {
+                            '%' => ModuleKind::FlipFlop,
+                            '&' => ModuleKind::Conjunction,
+                            _ => panic!(),
+                        },
+                        None => ModuleKind::Broadcast,
+                    };
+                    let module = Module { kind, destinations };
+                    (id, module)
+                },
+            ),
+        ),
+        |entries| Network {
+            modules: entries.into_iter().collect(),
+        },
+    )(input);
+    finish(input, result)
+}
+
+pub fn solve_part1(network: &Network) -> usize {
+    let mut activity = NetworkActivity::new(network, None);
+    for _ in 0..1000 {
+        activity.press_button();
+    }
+    activity.pulse_count(Energy::Low) * activity.pulse_count(Energy::High)
+}
+
+/// Number of button presses needed before `rx` receives a low pulse, or `None` if `rx` is not
+/// fed by a single conjunction or that count does not fit in a `u64`.
+///
+/// Each generator feeding the conjunction in front of `rx` is assumed to send high pulses
+/// periodically from its first one on.
+pub fn solve_part2(network: &Network, budget: &mut Budget) -> Result<Option<u64>, Timeout> {
+    let Some(rx_source) = network.rx_conjunction() else {
+        return Ok(None);
+    };
+    let mut activity = NetworkActivity::new(network, Some(rx_source));
+    let generators: HashSet<_> = activity
+        .state
+        .conjunction_states
+        .get(rx_source)
+        .map(|s| s.keys().copied().collect())
+        .unwrap();
+    let mut history: HashMap<_, Vec<_>> = HashMap::new();
+    for presses in 1u64.. {
+        budget.spend(|| {
+            format!(
+                "{} of the {} generators seen twice after {} presses",
+                history.values().filter(|v: &&Vec<_>| v.len() >= 2).count(),
+                generators.len(),
+                presses - 1
+            )
+        })?;
+        activity.press_button();
+        let high_pulses: Vec<_> = activity
+            .probe_signal
+            .iter()
+            .filter(|(_, e)| e.is_high())
+            .map(|(id, _)| id)
+            .collect();
+        let change = !high_pulses.is_empty();
+        for &p in high_pulses {
+            match history.entry(p) {
+                std::collections::hash_map::Entry::Occupied(mut entry) => {
+                    entry.get_mut().push(presses)
+                }
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert(vec![presses]);
+                }
+            };
+        }
+        if change && history.len() == generators.len() && history.values().all(|v| v.len() >= 2) {
+            let first = history.values().map(|v| v[0]).max().unwrap_or_default();
+            let Some(solutions) = math::crt(
+                history
+                    .values()
+                    .map(|v| (v[v.len() - 1], v[v.len() - 1] - v[v.len() - 2])),
+            ) else {
+                return Ok(None);
+            };
+            return Ok(math::least_at_or_above(solutions, first));
+        }
+    }
+    Ok(None)
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let network = parse_input(input)?;
+    let count = |predicate: fn(&ModuleKind) -> bool| {
+        network
+            .modules
+            .values()
+            .filter(|m| predicate(&m.kind))
+            .count()
+            .to_string()
+    };
+    let connections: usize = network.modules.values().map(|m| m.destinations.len()).sum();
+    Ok(vec![
+        ("modules", network.modules.len().to_string()),
+        ("broadcasters", count(ModuleKind::is_broadcast)),
+        ("flip-flops", count(ModuleKind::is_flip_flop)),
+        ("conjunctions", count(ModuleKind::is_conjunction)),
+        ("connections", connections.to_string()),
+    ])
+}
+
+pub(crate) fn checks(input: &str) -> Result<Vec<Check>, ParseError> {
+    let network = parse_input(input)?;
+    Ok(vec![(
+        "rx is fed by a single conjunction",
+        network.rx_conjunction().is_some(),
+    )])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_input, solve_part1, solve_part2};
+    use crate::budget::Budget;
+
+    const INPUT: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    #[test]
+    fn test_solve_part1() {
+        assert_eq!(solve_part1(&parse_input(INPUT).unwrap()), 32_000_000);
+    }
+
+    #[test]
+    fn test_solve_part2_timeout() {
+        let network = parse_input("broadcaster -> a\n%a -> con\n%g -> con\n&con -> rx").unwrap();
+        let timeout = solve_part2(&network, &mut Budget::unlimited().with_steps(1000)).unwrap_err();
+        assert_eq!(
+            timeout.progress,
+            "1 of the 2 generators seen twice after 1000 presses"
+        );
+        let network = parse_input("broadcaster -> a\n%a -> con\n&con -> rx").unwrap();
+        assert_eq!(solve_part2(&network, &mut Budget::unlimited()), Ok(Some(1)));
+    }
+
+    #[test]
+    fn test_solve_part2_without_rx() {
+        let network = parse_input("d -> Y").unwrap();
+        assert_eq!(solve_part2(&network, &mut Budget::unlimited()), Ok(None));
+    }
+}
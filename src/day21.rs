@@ -0,0 +1,276 @@
+use std::mem::swap;
+
+use itertools::Itertools;
+
+use crate::{geometry::Point2, hash::HashSet, parse::grid, report::Check, stats::Stat, ParseError};
+
+type Coord = i16;
+pub type Loc = Point2<Coord>;
+
+#[derive(Debug)]
+pub struct Garden {
+    rows: usize,
+    cols: usize,
+    open_plots: HashSet<Loc>,
+}
+
+impl Garden {
+    fn iter<'a>(&'a self, start: Loc) -> GardenIterator<'a> {
+        GardenIterator::new(self, start)
+    }
+
+    fn reachable_open_plots(&self, start: Loc, steps: usize) -> usize {
+        self.iter(start).nth(steps).unwrap()
+    }
+
+    fn is_well_behaved(&self, start: Loc) -> bool {
+        self.is_centred(start) && self.has_open_lanes(start)
+    }
+
+    fn is_centred(&self, start: Loc) -> bool {
+        (self.cols == self.rows)
+            && (start.x as usize * 2) == self.rows - 1
+            && (start.y as usize * 2) == self.cols - 1
+    }
+
+    fn has_open_lanes(&self, start: Loc) -> bool {
+        (0..self.rows).all(|row| {
+            [0, start.y, self.cols as Coord - 1]
+                .into_iter()
+                .all(|col| self.open_plots.contains(&Point2::new(row as Coord, col)))
+        }) && (0..self.cols).all(|col| {
+            [0, start.x, self.rows as Coord - 1]
+                .into_iter()
+                .all(|row| self.open_plots.contains(&Point2::new(row, col as Coord)))
+        })
+    }
+}
+
+struct GardenIterator<'a> {
+    parent: &'a Garden,
+    current: GardenIteratorState,
+    next: GardenIteratorState,
+}
+
+impl<'a> Iterator for GardenIterator<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let res = self.current.set.len();
+        while let Some(base) = self.current.front.pop() {
+            for candidate in base.neighbours() {
+                if self.parent.open_plots.contains(&candidate) && self.next.set.insert(candidate) {
+                    self.next.front.push(candidate);
+                }
+            }
+        }
+        swap(&mut self.current, &mut self.next);
+        Some(res)
+    }
+}
+
+impl<'a> GardenIterator<'a> {
+    fn new(parent: &'a Garden, start: Loc) -> Self {
+        Self {
+            parent,
+            current: GardenIteratorState::new(start),
+            next: GardenIteratorState::default(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct GardenIteratorState {
+    set: HashSet<Loc>,
+    front: Vec<Loc>,
+}
+
+impl GardenIteratorState {
+    fn new(start: Loc) -> Self {
+        Self {
+            set: HashSet::from_iter([start]),
+            front: vec![start],
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<(Garden, Loc), ParseError> {
+    let tiles = grid(input, |tile| match tile {
+        '.' => Some((true, false)),
+        '#' => Some((false, false)),
+        'S' => Some((true, true)),
+        _ => None,
+    })?;
+    let (locs, is_open, is_start): (Vec<_>, Vec<_>, Vec<_>) = tiles
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .map(move |(col, &(is_open, is_start))| {
+                    (Point2::new(row as Coord, col as Coord), is_open, is_start)
+                })
+        })
+        .multiunzip();
+    let start = is_start
+        .into_iter()
+        .zip(locs.iter())
+        .filter_map(|(b, l)| b.then_some(*l))
+        .next()
+        .ok_or(ParseError {
+            offset: input.len(),
+        })?;
+    Ok((
+        Garden {
+            rows: tiles.len(),
+            cols: tiles.first().map_or(0, Vec::len),
+            open_plots: is_open
+                .into_iter()
+                .zip(locs.iter())
+                .filter_map(|(b, l)| b.then_some(*l))
+                .collect(),
+        },
+        start,
+    ))
+}
+
+pub fn solve_part1(garden: &Garden, start: Loc) -> usize {
+    garden.reachable_open_plots(start, 64)
+}
+
+/// Whether the garden is shaped as `solve_part2` assumes: well behaved, and such that the
+/// plots reachable from the start, the corners and the middles of the edges alternate between
+/// two counts once they have had time to fill it.
+fn fits_part2(garden: &Garden, start: Loc) -> bool {
+    let size = garden.cols / 2;
+    if size == 0 || !garden.is_well_behaved(start) {
+        return false;
+    }
+    let settles = |root, steps| {
+        let reachable = |steps| garden.reachable_open_plots(root, steps);
+        reachable(steps - 2) != reachable(steps)
+            && reachable(steps - 1) == reachable(steps + 1)
+            && reachable(steps) == reachable(steps + 2)
+    };
+    let corners = [
+        Point2::new(0, 0),
+        Point2::new(0, garden.cols as Coord - 1),
+        Point2::new(garden.rows as Coord - 1, 0),
+        Point2::new(garden.rows as Coord - 1, garden.cols as Coord - 1),
+    ];
+    let edges = [
+        Point2::new(start.x, 0),
+        Point2::new(start.x, garden.cols as Coord - 1),
+        Point2::new(0, start.y),
+        Point2::new(garden.rows as Coord - 1, start.y),
+    ];
+    settles(start, 2 * size)
+        && corners.into_iter().all(|root| settles(root, 4 * size))
+        && edges.into_iter().all(|root| settles(root, 3 * size))
+}
+
+fn even_integer_sum(bound: usize) -> usize {
+    let count = bound / 2;
+    count * (count + 1)
+}
+
+fn odd_integer_sum(bound: usize) -> usize {
+    let count = bound.div_ceil(2);
+    count * count
+}
+
+/// Number of plots reachable in exactly 26501365 steps across the infinitely repeated garden,
+/// or `None` if the garden is not shaped as the puzzle's is.
+pub fn solve_part2(garden: &Garden, start: Loc) -> Option<usize> {
+    if !fits_part2(garden, start) {
+        return None;
+    }
+    // A centred garden has an odd side, with the start in the middle.
+    let period = garden.rows;
+    let distance = garden.rows / 2;
+    let target_steps = 26_501_365;
+    let periods = target_steps / period;
+    let remainder = target_steps - (period * periods);
+    if periods == 0 || remainder != distance {
+        return None;
+    }
+    let (even_count, odd_count) = {
+        let mut iter = garden.iter(start).skip(2 * distance);
+        (iter.next().unwrap(), iter.next().unwrap())
+    };
+    assert_eq!(target_steps % 2, 1);
+    let full_count = odd_count
+        + 4 * (even_count * odd_integer_sum(periods - 1)
+            + odd_count * even_integer_sum(periods - 1));
+    let point_count = [
+        Point2::new(start.x, 0),
+        Point2::new(start.x, start.y * 2),
+        Point2::new(0, start.y),
+        Point2::new(start.x * 2, start.y),
+    ]
+    .into_iter()
+    .map(|root| garden.reachable_open_plots(root, distance + remainder))
+    .sum::<usize>();
+    let fat_count = [
+        Point2::new(0, 0),
+        Point2::new(0, start.y * 2),
+        Point2::new(start.x * 2, 0),
+        Point2::new(start.x * 2, start.y * 2),
+    ]
+    .into_iter()
+    .map(|root| garden.reachable_open_plots(root, 2 * distance + remainder))
+    .sum::<usize>()
+        * (periods - 1);
+    let slim_count = [
+        Point2::new(0, 0),
+        Point2::new(0, start.y * 2),
+        Point2::new(start.x * 2, 0),
+        Point2::new(start.x * 2, start.y * 2),
+    ]
+    .into_iter()
+    .map(|root| garden.reachable_open_plots(root, remainder - 1))
+    .sum::<usize>()
+        * periods;
+    Some(full_count + point_count + fat_count + slim_count)
+}
+
+pub(crate) fn checks(input: &str) -> Result<Vec<Check>, ParseError> {
+    let (garden, start) = parse_input(input)?;
+    Ok(vec![
+        (
+            "the garden is square with the start at its centre",
+            garden.is_centred(start),
+        ),
+        (
+            "the edges and the start's row and column are open",
+            garden.has_open_lanes(start),
+        ),
+    ])
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let (garden, start) = parse_input(input)?;
+    Ok(vec![
+        ("rows", garden.rows.to_string()),
+        ("columns", garden.cols.to_string()),
+        ("start", start.to_string()),
+        ("open plots", garden.open_plots.len().to_string()),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_misshapen_gardens() {
+        let (garden, start) = parse_input("S").unwrap();
+        assert_eq!(solve_part1(&garden, start), 1);
+        assert_eq!(solve_part2(&garden, start), None);
+        let (garden, start) = parse_input("S\nS").unwrap();
+        assert_eq!(solve_part1(&garden, start), 1);
+        assert_eq!(solve_part2(&garden, start), None);
+        let (garden, start) = parse_input("...\n.S.\n...").unwrap();
+        assert_eq!(solve_part2(&garden, start), Some(26501366 * 26501366));
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    geometry::{Cuboid, Point2, Point3},
+    stats::Stat,
+    ParseError,
+};
+
+type Coord = i16;
+type Extents = HashMap<Point2<Coord>, Vec<(usize, (Coord, Coord))>>;
+
+#[derive(Debug)]
+pub struct Brick {
+    cubes: Cuboid<Coord>,
+}
+
+impl Brick {
+    /// The brick running from `first` to `second`, or `None` if they are not on a line along
+    /// one axis or one lies below the ground, at a height under 1.
+    fn new(first: Point3<Coord>, second: Point3<Coord>) -> Option<Self> {
+        let cubes = Cuboid::spanning(first, second);
+        let Cuboid { min, max } = cubes;
+        let axes = [min.x != max.x, min.y != max.y, min.z != max.z];
+        (axes.into_iter().filter(|&differs| differs).count() <= 1 && min.z >= 1)
+            .then_some(Self { cubes })
+    }
+
+    /// The range of heights the brick spans above each point of its shadow.
+    fn z_extents(&self) -> Vec<(Point2<Coord>, (Coord, Coord))> {
+        let Cuboid { min, max } = self.cubes;
+        (min.x..=max.x)
+            .flat_map(|x| (min.y..=max.y).map(move |y| (Point2::new(x, y), (min.z, max.z))))
+            .collect()
+    }
+}
+
+fn z_extents(bricks: &[Brick]) -> Extents {
+    let mut res: HashMap<_, Vec<_>> = HashMap::new();
+    for (rank, brick) in bricks.iter().enumerate() {
+        for (col, extent) in brick.z_extents() {
+            res.entry(col).or_default().push((rank, extent));
+        }
+    }
+    for v in res.values_mut() {
+        v.sort_unstable_by_key(|(_, (z_lower, _))| *z_lower);
+    }
+    res
+}
+
+fn parse_loc(input: &str, loc: &str) -> Result<Point3<Coord>, ParseError> {
+    let mut iter = loc
+        .split(',')
+        .map(|s| s.parse().map_err(|_| ParseError::at(input, s)));
+    let mut next = || {
+        iter.next()
+            .unwrap_or_else(|| Err(ParseError::at(input, &loc[loc.len()..])))
+    };
+    Ok(Point3::new(next()?, next()?, next()?))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Brick>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_once('~')
+                .ok_or_else(|| ParseError::at(input, line))
+                .and_then(|(f, s)| {
+                    Brick::new(parse_loc(input, f)?, parse_loc(input, s)?)
+                        .ok_or_else(|| ParseError::at(input, line))
+                })
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct Stack<'a> {
+    bricks: &'a [Brick],
+    extents: Extents,
+}
+
+impl<'a> Stack<'a> {
+    fn new(bricks: &'a [Brick]) -> Self {
+        Self {
+            bricks,
+            extents: z_extents(bricks),
+        }
+    }
+
+    fn settle(&mut self) {
+        let mut ranks: Vec<_> = self
+            .bricks
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (i, b.cubes.min.z))
+            .collect();
+        ranks.sort_unstable_by_key(|(_, z_lowest)| *z_lowest);
+        let mut offsets = vec![0; self.bricks.len()];
+        loop {
+            let mut no_change = true;
+            for (index, _) in ranks.iter() {
+                let mut gap = Coord::MAX;
+                for (col, (z_low, _)) in self.bricks[*index].z_extents() {
+                    let col_extents = &self.extents[&col];
+                    let col_rank = col_extents
+                        .binary_search_by_key(&z_low, |(_, (l, _))| *l)
+                        .unwrap();
+                    let z_support = if col_rank > 0 {
+                        let (other_index, (_, other_high)) = col_extents[col_rank - 1];
+                        other_high - offsets[other_index]
+                    } else {
+                        0
+                    };
+                    gap = gap.min((z_low - offsets[*index]) - (z_support + 1));
+                }
+                if gap > 0 {
+                    no_change = false;
+                    offsets[*index] += gap;
+                }
+            }
+            if no_change {
+                break;
+            }
+            ranks.sort_unstable_by_key(|(index, z_lowest)| *z_lowest - offsets[*index]);
+        }
+        for col in self.extents.values_mut() {
+            for (index, (low, high)) in col {
+                let offset = offsets[*index];
+                *low -= offset;
+                *high -= offset;
+            }
+        }
+    }
+
+    fn supports(&self) -> Vec<HashSet<usize>> {
+        let mut res: Vec<_> = vec![HashSet::default(); self.bricks.len()];
+        for col in self.extents.values() {
+            for pair in col.windows(2) {
+                if pair[0].1 .1 == pair[1].1 .0 - 1 {
+                    res[pair[1].0].insert(pair[0].0);
+                }
+            }
+        }
+        res
+    }
+
+    fn dependencies(&self) -> Vec<HashSet<usize>> {
+        let mut res: Vec<_> = vec![HashSet::default(); self.bricks.len()];
+        for col in self.extents.values() {
+            for pair in col.windows(2) {
+                if pair[0].1 .1 == pair[1].1 .0 - 1 {
+                    res[pair[0].0].insert(pair[1].0);
+                }
+            }
+        }
+        res
+    }
+}
+
+pub fn solve_part1(bricks: &[Brick]) -> usize {
+    let mut stack = Stack::new(bricks);
+    stack.settle();
+    let key_bricks: HashSet<_> = stack
+        .supports()
+        .into_iter()
+        .filter_map(|supports| {
+            if supports.len() == 1 {
+                return supports.into_iter().next();
+            }
+            None
+        })
+        .collect();
+    bricks.len() - key_bricks.len()
+}
+
+pub fn solve_part2(bricks: &[Brick]) -> usize {
+    let mut stack = Stack::new(bricks);
+    stack.settle();
+    let supports = stack.supports();
+    let key_bricks: HashSet<_> = supports
+        .iter()
+        .filter_map(|supports| {
+            if supports.len() == 1 {
+                return supports.iter().next();
+            }
+            None
+        })
+        .collect();
+    let dependencies = stack.dependencies();
+    key_bricks
+        .into_iter()
+        .map(|&root| {
+            let mut removed = HashSet::from([root]);
+            let mut front = removed.clone();
+            loop {
+                let falling: Vec<_> = front
+                    .drain()
+                    .flat_map(|falling_id| {
+                        dependencies[falling_id]
+                            .iter()
+                            .copied()
+                            .filter(|&candidate_id| supports[candidate_id].is_subset(&removed))
+                    })
+                    .collect();
+                if falling.is_empty() {
+                    break;
+                }
+                front.extend(falling);
+                removed.extend(front.iter())
+            }
+            removed.len() - 1
+        })
+        .sum()
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let bricks = parse_input(input)?;
+    let lowest = bricks
+        .iter()
+        .map(|b| b.cubes.min.z)
+        .min()
+        .unwrap_or_default();
+    let highest = bricks
+        .iter()
+        .map(|b| b.cubes.max.z)
+        .max()
+        .unwrap_or_default();
+    Ok(vec![
+        ("bricks", bricks.len().to_string()),
+        ("lowest z", lowest.to_string()),
+        ("highest z", highest.to_string()),
+        ("height span", (highest - lowest + 1).to_string()),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    #[test]
+    fn test_solve_part1() {
+        let answer = solve_part1(&parse_input(INPUT).unwrap());
+        assert_eq!(answer, 5);
+    }
+    #[test]
+    fn test_solve_part2() {
+        let answer = solve_part2(&parse_input(INPUT).unwrap());
+        assert_eq!(answer, 7);
+    }
+
+    #[test]
+    fn test_misplaced_bricks() {
+        assert_eq!(
+            parse_input("1,0,1~1,2,1\n8222,2222,2~2,2,3").unwrap_err(),
+            ParseError { offset: 12 }
+        );
+        assert!(parse_input("1,0,0~1,2,0").is_err());
+        assert!(parse_input("1,0,-3~1,0,2").is_err());
+        assert!(parse_input("1,0,1~1,0,1\n1,0,2~1,0,32767").is_ok());
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(INPUT).unwrap();
+        assert_eq!(stats[0], ("bricks", "7".to_string()));
+        assert_eq!(stats[3], ("height span", "9".to_string()));
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    budget::{Budget, Timeout},
+    geometry::{Dir4, Point2},
+    report::Rendering,
+    stats::Stat,
+    ParseError,
+};
+
+type Coord = i16;
+type Loc = Point2<Coord>;
+type Edges = HashMap<Loc, Vec<(Dir4, Loc, u64)>>;
+
+#[derive(Debug)]
+enum Tile {
+    Flat,
+    Slope(Dir4),
+}
+
+#[derive(Debug)]
+pub struct Terrain {
+    tiles: HashMap<Loc, Tile>,
+    rows: Coord,
+    cols: Coord,
+}
+
+impl Terrain {
+    fn new(tiles: HashMap<Loc, Tile>) -> Self {
+        let (rows, cols) = tiles.iter().fold((0, 0), |acc, (loc, _)| {
+            (acc.0.max(loc.x + 1), acc.1.max(loc.y + 2))
+        });
+        Self { tiles, rows, cols }
+    }
+
+    fn entry(&self) -> Loc {
+        Point2::new(0, 1)
+    }
+
+    fn exit(&self) -> Loc {
+        Point2::new(self.rows - 1, self.cols - 2)
+    }
+
+    /// Whether `loc` is the entry or the exit, which are nodes of the trails whatever their
+    /// neighbours.
+    fn is_end(&self, loc: &Loc) -> bool {
+        *loc == self.entry() || *loc == self.exit()
+    }
+
+    fn valid_dry_moves_from<'a>(&'a self, loc: &'a Loc) -> impl Iterator<Item = (Dir4, Loc)> + 'a {
+        Dir4::ALL
+            .into_iter()
+            .map(|dir| (dir, loc.step(dir)))
+            .filter(|(_, next_loc)| self.tiles.contains_key(next_loc))
+    }
+
+    fn valid_icy_moves_from<'a>(&'a self, loc: &'a Loc) -> impl Iterator<Item = (Dir4, Loc)> + 'a {
+        Dir4::ALL
+            .into_iter()
+            .map(|dir| (dir, loc.step(dir)))
+            .filter(|(dir, next_loc)| match self.tiles.get(next_loc) {
+                Some(tile) => match tile {
+                    Tile::Flat => true,
+                    Tile::Slope(slope) => dir == slope,
+                },
+                None => false,
+            })
+    }
+
+    fn dry_nodes(&self) -> HashMap<Loc, Vec<Dir4>> {
+        self.tiles
+            .keys()
+            .map(|loc| {
+                (
+                    *loc,
+                    self.valid_dry_moves_from(loc)
+                        .map(|(d, _)| d)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .filter(|(loc, v)| v.len() != 2 || self.is_end(loc))
+            .collect()
+    }
+
+    fn icy_nodes(&self) -> HashMap<Loc, Vec<Dir4>> {
+        self.tiles
+            .keys()
+            .map(|loc| {
+                (
+                    *loc,
+                    self.valid_dry_moves_from(loc)
+                        .map(|(d, _)| d)
+                        .collect::<Vec<_>>(),
+                    self.valid_icy_moves_from(loc)
+                        .map(|(d, _)| d)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .filter(|(loc, v, _)| v.len() != 2 || self.is_end(loc))
+            .map(|(l, d, _)| (l, d))
+            .collect()
+    }
+
+    fn dry_edges(&self) -> Edges {
+        let nodes = self.dry_nodes();
+        let mut res = Edges::new();
+        for (node_loc, node_dirs) in nodes.iter() {
+            for dir in node_dirs {
+                let mut weight = 1;
+                let mut prev_dir = *dir;
+                let mut curr_loc = node_loc.step(prev_dir);
+                while !nodes.contains_key(&curr_loc) {
+                    (prev_dir, curr_loc) = self
+                        .valid_dry_moves_from(&curr_loc)
+                        .find(|(d, _)| *d != prev_dir.opposite())
+                        .unwrap();
+                    weight += 1;
+                }
+                res.entry(*node_loc)
+                    .or_default()
+                    .push((*dir, curr_loc, weight));
+            }
+        }
+        res
+    }
+
+    fn icy_edges(&self) -> Edges {
+        let nodes = self.icy_nodes();
+        let mut res = Edges::new();
+        for (node_loc, node_dirs) in nodes.iter() {
+            for dir in node_dirs {
+                let mut weight = 1;
+                let mut prev_dir = *dir;
+                let mut curr_loc = node_loc.step(prev_dir);
+                loop {
+                    if nodes.contains_key(&curr_loc) {
+                        res.entry(*node_loc)
+                            .or_default()
+                            .push((*dir, curr_loc, weight));
+                        break;
+                    }
+                    if let Some((curr_dir, next_loc)) = self
+                        .valid_icy_moves_from(&curr_loc.clone())
+                        .find(|(d, _)| *d != prev_dir.opposite())
+                    {
+                        prev_dir = curr_dir;
+                        curr_loc = next_loc;
+                        weight += 1;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Parses the map, which must have a trail at its entry and at its exit.
+pub fn parse_input(input: &str) -> Result<Terrain, ParseError> {
+    let terrain = Terrain::new(
+        input
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.char_indices()
+                    .filter(|&(_, c)| c != '#')
+                    .map(move |(col, c)| {
+                        match c {
+                            '.' => Ok(Tile::Flat),
+                            '^' => Ok(Tile::Slope(Dir4::North)),
+                            '>' => Ok(Tile::Slope(Dir4::East)),
+                            'v' => Ok(Tile::Slope(Dir4::South)),
+                            '<' => Ok(Tile::Slope(Dir4::West)),
+                            _ => Err(ParseError::at(input, &line[col..])),
+                        }
+                        .map(|tile| (Point2::new(row as Coord, col as Coord), tile))
+                    })
+            })
+            .collect::<Result<_, _>>()?,
+    );
+    if !terrain.tiles.contains_key(&terrain.entry()) || !terrain.tiles.contains_key(&terrain.exit())
+    {
+        return Err(ParseError {
+            offset: input.len(),
+        });
+    }
+    Ok(terrain)
+}
+
+/// A hike from the entry to the exit: its length and the nodes it leaves with the direction it
+/// takes, in order.
+#[derive(Debug)]
+struct Hike {
+    length: u64,
+    steps: Vec<(Loc, Dir4)>,
+}
+
+/// A depth-first search for the longest hike to `goal` along `edges`, which spends a step of
+/// `budget` per node it enters and keeps the longest hike found so far in `best`.
+struct Search<'a> {
+    edges: &'a Edges,
+    goal: Loc,
+    budget: &'a mut Budget,
+    path: Vec<(Loc, Dir4)>,
+    best: Option<Hike>,
+}
+
+impl<'a> Search<'a> {
+    fn new(edges: &'a Edges, goal: Loc, budget: &'a mut Budget) -> Self {
+        Self {
+            edges,
+            goal,
+            budget,
+            path: Vec::new(),
+            best: None,
+        }
+    }
+
+    /// The length of the longest hike found so far, 0 before the first.
+    fn best_length(&self) -> u64 {
+        self.best.as_ref().map_or(0, |hike| hike.length)
+    }
+
+    fn explore(
+        &mut self,
+        mut visited: HashSet<Loc>,
+        node: Loc,
+        path_length: u64,
+    ) -> Result<(), Timeout> {
+        let best = self.best_length();
+        self.budget
+            .spend(|| format!("the longest hike found so far takes {best} steps"))?;
+        visited.insert(node);
+        for &(dir, next_node, weight) in self.edges.get(&node).into_iter().flatten() {
+            if !visited.contains(&next_node) {
+                self.path.push((node, dir));
+                if next_node == self.goal {
+                    if path_length + weight > self.best_length() {
+                        self.best = Some(Hike {
+                            length: path_length + weight,
+                            steps: self.path.clone(),
+                        });
+                    }
+                } else {
+                    self.explore(visited.clone(), next_node, path_length + weight)?;
+                }
+                self.path.pop();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The length of the longest hike down the icy slopes, or `None` if there is no hike.
+pub fn solve_part1(terrain: &Terrain, budget: &mut Budget) -> Result<Option<u64>, Timeout> {
+    let edges = terrain.icy_edges();
+    let mut search = Search::new(&edges, terrain.exit(), budget);
+    search.explore(HashSet::new(), terrain.entry(), 0)?;
+    Ok(search.best.map(|hike| hike.length))
+}
+
+/// The length of the longest hike on dry slopes, or `None` if there is no hike.
+pub fn solve_part2(terrain: &Terrain, budget: &mut Budget) -> Result<Option<u64>, Timeout> {
+    let edges = terrain.dry_edges();
+    let mut search = Search::new(&edges, terrain.exit(), budget);
+    search.explore(HashSet::new(), terrain.entry(), 0)?;
+    Ok(search.best.map(|hike| hike.length))
+}
+
+/// Draws the trails, highlighting the longest hike down the icy slopes that part 1 finds
+/// within `budget`.
+pub(crate) fn render(input: &str, budget: &mut Budget) -> Result<Rendering, ParseError> {
+    let terrain = parse_input(input)?;
+    let nodes = terrain.icy_nodes();
+    let edges = terrain.icy_edges();
+    let mut search = Search::new(&edges, terrain.exit(), budget);
+    // A search cut short still draws the longest hike it has found.
+    let _ = search.explore(HashSet::new(), terrain.entry(), 0);
+    let mut trail = HashSet::from([terrain.exit()]);
+    for (node, dir) in search.best.map(|hike| hike.steps).unwrap_or_default() {
+        trail.insert(node);
+        let (mut prev_dir, mut loc) = (dir, node.step(dir));
+        while !nodes.contains_key(&loc) {
+            trail.insert(loc);
+            (prev_dir, loc) = terrain
+                .valid_icy_moves_from(&loc.clone())
+                .find(|(d, _)| *d != prev_dir.opposite())
+                .expect("edges only follow trails that lead to a node");
+        }
+    }
+    Ok((0..terrain.rows)
+        .map(|row| {
+            (0..terrain.cols)
+                .map(|col| {
+                    let symbol = match terrain.tiles.get(&Point2::new(row, col)) {
+                        None => '#',
+                        Some(Tile::Flat) => '.',
+                        Some(Tile::Slope(Dir4::North)) => '^',
+                        Some(Tile::Slope(Dir4::East)) => '>',
+                        Some(Tile::Slope(Dir4::South)) => 'v',
+                        Some(Tile::Slope(Dir4::West)) => '<',
+                    };
+                    (symbol, trail.contains(&Point2::new(row, col)))
+                })
+                .collect()
+        })
+        .collect())
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let terrain = parse_input(input)?;
+    let [entry, exit] = [terrain.entry(), terrain.exit()];
+    let slopes = terrain
+        .tiles
+        .values()
+        .filter(|t| matches!(t, Tile::Slope(_)))
+        .count();
+    Ok(vec![
+        ("rows", terrain.rows.to_string()),
+        ("columns", terrain.cols.to_string()),
+        ("start", entry.to_string()),
+        ("target", exit.to_string()),
+        ("paths", terrain.tiles.len().to_string()),
+        ("slopes", slopes.to_string()),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    const INPUT: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn test_solve_part1() {
+        let terrain = parse_input(INPUT).unwrap();
+        assert_eq!(
+            solve_part1(&terrain, &mut Budget::unlimited()),
+            Ok(Some(94))
+        );
+    }
+
+    #[test]
+    fn test_odd_maps() {
+        assert_eq!(parse_input("").unwrap_err(), ParseError { offset: 0 });
+        assert!(parse_input("#..#\n#.##").is_err());
+        let terrain = parse_input("#.###\n#...#\n###.#").unwrap();
+        assert_eq!(solve_part1(&terrain, &mut Budget::unlimited()), Ok(Some(4)));
+        let terrain = parse_input("#.###\n##..#\n###.#").unwrap();
+        assert_eq!(solve_part1(&terrain, &mut Budget::unlimited()), Ok(None));
+        assert_eq!(solve_part2(&terrain, &mut Budget::unlimited()), Ok(None));
+        let terrain = parse_input("...\n...").unwrap();
+        assert_eq!(solve_part2(&terrain, &mut Budget::unlimited()), Ok(Some(4)));
+    }
+
+    #[test]
+    fn test_render() {
+        let rendering = render(INPUT, &mut Budget::unlimited()).unwrap();
+        assert_eq!(rendering.len(), 23);
+        assert_eq!(rendering[0][1], ('.', true));
+        assert_eq!(rendering[3][10], ('>', true));
+        let steps = rendering.iter().flatten().filter(|(_, h)| *h).count();
+        assert_eq!(steps, 94 + 1);
+        let rendering = render(INPUT, &mut Budget::unlimited().with_steps(0)).unwrap();
+        let steps = rendering.iter().flatten().filter(|(_, h)| *h).count();
+        assert_eq!(steps, 1);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let terrain = parse_input(INPUT).unwrap();
+        let mut budget = Budget::unlimited();
+        assert_eq!(solve_part2(&terrain, &mut budget), Ok(Some(154)));
+        let timeout = solve_part2(&terrain, &mut Budget::unlimited().with_steps(10)).unwrap_err();
+        assert_eq!(timeout.steps, 10);
+        assert!(timeout
+            .progress
+            .starts_with("the longest hike found so far takes"));
+    }
+}
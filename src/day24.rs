@@ -0,0 +1,266 @@
+use itertools::Itertools;
+
+use nalgebra::{Const, Matrix3, OMatrix, RowVector3, Vector3};
+
+use crate::{
+    geometry::{Point2, Point3},
+    ParseError,
+};
+
+type Coord = i64;
+
+/// Wide enough for the cross products of any parsed coordinates.
+type Wide = i128;
+
+/// The largest coordinate allowed, the last integer up to which every `f64` is exact, since
+/// both parts compute with the coordinates as floating-point numbers.
+const MAX_COORD: Coord = 1 << f64::MANTISSA_DIGITS;
+
+#[derive(Debug)]
+pub struct Hailstone<P> {
+    position: P,
+    velocity: P,
+}
+
+impl Hailstone<Point3<Coord>> {
+    fn projection(&self) -> Hailstone<Point2<Wide>> {
+        let widen = |p: Point3<Coord>| Point2::new(p.x.into(), p.y.into());
+        Hailstone {
+            position: widen(self.position),
+            velocity: widen(self.velocity),
+        }
+    }
+}
+
+fn future_intersection(
+    a: &Hailstone<Point2<Wide>>,
+    b: &Hailstone<Point2<Wide>>,
+) -> Option<[f64; 2]> {
+    let det = b.velocity.cross(a.velocity);
+    if det == 0 {
+        return None;
+    }
+    let relative_position = b.position - a.position;
+    let a_time = b.velocity.cross(relative_position);
+    if a_time.signum() != det.signum() {
+        return None;
+    }
+    let b_time = a.velocity.cross(relative_position);
+    if b_time.signum() != det.signum() {
+        return None;
+    }
+    Some([
+        a.position.x as f64 + a.velocity.x as f64 * (a_time as f64 / det as f64),
+        a.position.y as f64 + a.velocity.y as f64 * (a_time as f64 / det as f64),
+    ])
+}
+
+fn in_range<const LOWER_BOUND: Coord, const UPPER_BOUND: Coord>(coord: f64) -> bool {
+    LOWER_BOUND as f64 <= coord && coord <= UPPER_BOUND as f64
+}
+
+fn parse_loc3(input: &str, loc: &str) -> Result<Point3<Coord>, ParseError> {
+    let mut iter = loc.split(", ");
+    let mut parse = || {
+        iter.next()
+            .ok_or_else(|| ParseError::at(input, &loc[loc.len()..]))
+            .and_then(|s| {
+                s.trim()
+                    .parse()
+                    .ok()
+                    .filter(|c: &Coord| c.abs() <= MAX_COORD)
+                    .ok_or_else(|| ParseError::at(input, s))
+            })
+    };
+    Ok(Point3::new(parse()?, parse()?, parse()?))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Hailstone<Point3<Coord>>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_once(" @ ")
+                .ok_or_else(|| ParseError::at(input, line))
+                .and_then(|(p, v)| {
+                    Ok(Hailstone {
+                        position: parse_loc3(input, p.trim())?,
+                        velocity: parse_loc3(input, v.trim())?,
+                    })
+                })
+        })
+        .collect()
+}
+
+pub fn solve_part1<const LOWER_BOUND: Coord, const UPPER_BOUND: Coord>(
+    hailstones: &[Hailstone<Point3<Coord>>],
+) -> usize {
+    let hailstones: Vec<_> = hailstones.iter().map(|h| h.projection()).collect();
+    hailstones
+        .iter()
+        .combinations(2)
+        .filter_map(|pair| {
+            future_intersection(pair[0], pair[1]).filter(|loc| {
+                in_range::<LOWER_BOUND, UPPER_BOUND>(loc[0])
+                    && in_range::<LOWER_BOUND, UPPER_BOUND>(loc[1])
+            })
+        })
+        .count()
+}
+
+type Data = OMatrix<f64, Const<3>, Const<3>>;
+type Residual = OMatrix<f64, Const<9>, Const<1>>;
+type Gradient = OMatrix<f64, Const<9>, Const<6>>;
+
+struct Problem {
+    positions: Data,
+    velocities: Data,
+}
+
+impl Problem {
+    /// The problem set by the first three hailstones, or `None` if there are fewer.
+    fn new(hailstones: &[Hailstone<Point3<Coord>>]) -> Option<Self> {
+        let hailstones = hailstones.get(..3)?;
+        let positions = Data::from_fn(|i, j| hailstones[j].position[i] as f64);
+        let velocities = Data::from_fn(|i, j| hailstones[j].velocity[i] as f64);
+        Some(Self {
+            positions,
+            velocities,
+        })
+    }
+
+    fn relax(value: &Vector3<Coord>) -> Vector3<f64> {
+        Vector3::from_fn(|i, _| value[i] as f64)
+    }
+
+    fn round(value: &Vector3<f64>) -> Vector3<Coord> {
+        Vector3::from_fn(|i, _| value[i].round() as Coord)
+    }
+
+    fn residual(&self, pos: &Vector3<f64>, vel: &Vector3<f64>) -> Residual {
+        let mut columns = self
+            .positions
+            .column_iter()
+            .zip(self.velocities.column_iter())
+            .map(|(p, v)| (pos - p).cross(&(vel - v)));
+        let columns = [
+            columns.next().unwrap(),
+            columns.next().unwrap(),
+            columns.next().unwrap(),
+        ];
+        Matrix3::from_columns(&columns).reshape_generic(Const::<9>, Const::<1>)
+    }
+
+    fn partial_gradient(state: &Vector3<f64>, data: &Data) -> OMatrix<f64, Const<9>, Const<3>> {
+        let delta = RowVector3::repeat(1.0).kronecker(&Matrix3::identity());
+        let diff =
+            (data - RowVector3::repeat(1.0).kronecker(state)).kronecker(&RowVector3::repeat(1.0));
+        OMatrix::<f64, Const<9>, Const<3>>::from_row_iterator(
+            delta
+                .column_iter()
+                .zip(diff.column_iter())
+                .flat_map(|(d, p)| {
+                    p.cross(&d)
+                        .into_iter()
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .into_iter()
+                }),
+        )
+    }
+
+    fn gradient(&self, pos: &Vector3<f64>, vel: &Vector3<f64>) -> Gradient {
+        let pos_gradient = Self::partial_gradient(vel, &self.velocities).scale(-1.0);
+        let vel_gradient = Self::partial_gradient(pos, &self.positions);
+        Gradient::from_iterator(pos_gradient.into_iter().chain(&vel_gradient).copied())
+    }
+
+    /// Refines the initial position and velocity of the rock until it hits every hailstone, or
+    /// returns `None` if a step of the refinement has no solution.
+    fn solve(
+        &self,
+        init_pos: &Vector3<Coord>,
+        init_vel: &Vector3<Coord>,
+    ) -> Option<(Vector3<Coord>, Vector3<Coord>)> {
+        let mut pos = Self::relax(init_pos);
+        let mut vel = Self::relax(init_vel);
+        let mut res = self.residual(&pos, &vel);
+        let mut iter = 1;
+        while res.norm() > 0.0 {
+            let gradient = self.gradient(&pos, &vel);
+            let qr = gradient.qr();
+            qr.q_tr_mul(&mut res);
+            let r = qr.unpack_r();
+            let increment = r
+                .fixed_rows::<6>(0)
+                .solve_upper_triangular(&res.fixed_rows::<6>(0))?;
+            pos -= increment.fixed_rows::<3>(0);
+            vel -= increment.fixed_rows::<3>(3);
+            iter += 1;
+            if iter > 100 {
+                break;
+            }
+            res = self.residual(&pos, &vel);
+        }
+        Some((Self::round(&pos), Self::round(&vel)))
+    }
+}
+
+/// The sum of the coordinates of the position from which a rock hits every hailstone, or
+/// `None` if there are fewer than three hailstones or they do not determine that position.
+pub fn solve_part2(hailstones: &[Hailstone<Point3<Coord>>]) -> Option<Coord> {
+    let problem = Problem::new(hailstones)?;
+    let (pos, _) = problem.solve(&Vector3::zeros(), &Vector3::zeros())?;
+    pos[0].checked_add(pos[1])?.checked_add(pos[2])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INPUT: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn test_solve_part1() {
+        assert_eq!(solve_part1::<7, 27>(&parse_input(INPUT).unwrap()), 2);
+    }
+
+    #[test]
+    fn test_problem_residual() {
+        let problem = Problem::new(&parse_input(INPUT).unwrap()).unwrap();
+        assert_eq!(
+            problem.residual(
+                &Problem::relax(&Vector3::new(24, 13, 10)),
+                &Problem::relax(&Vector3::new(-3, 1, 2))
+            ),
+            Residual::zeros()
+        );
+    }
+
+    #[test]
+    fn test_problem_solve() {
+        let problem = Problem::new(&parse_input(INPUT).unwrap()).unwrap();
+        let (pos, vel) = problem.solve(&Vector3::zeros(), &Vector3::zeros()).unwrap();
+        assert_eq!(pos, Vector3::new(24, 13, 10));
+        assert_eq!(vel, Vector3::new(-3, 1, 2));
+        assert_eq!(
+            solve_part2(&parse_input(INPUT).unwrap()),
+            Some(24 + 13 + 10)
+        );
+    }
+
+    #[test]
+    fn test_degenerate_hailstones() {
+        assert_eq!(solve_part2(&parse_input("").unwrap()), None);
+        let hailstones = parse_input("19, 13, 30 @ -2,  1, -2\n18, 19, 22 @ -1, -1, -2").unwrap();
+        assert_eq!(solve_part1::<7, 27>(&hailstones), 1);
+        assert_eq!(solve_part2(&hailstones), None);
+        let big = "9007199254740992, -9007199254740992, 0 @ -9007199254740992, 9007199254740992, 1";
+        let hailstones = parse_input(&format!("{big}\n0, 0, 0 @ 9007199254740992, 1, 0")).unwrap();
+        assert_eq!(solve_part1::<0, 1>(&hailstones), 0);
+        assert!(parse_input("9007199254740993, 0, 0 @ 0, 0, 0").is_err());
+    }
+}
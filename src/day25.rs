@@ -0,0 +1,87 @@
+use std::{
+    collections::{HashMap, HashSet},
+    iter::once,
+};
+
+use rustworkx_core::{connectivity::stoer_wagner_min_cut, petgraph::graph::UnGraph};
+
+use crate::{stats::Stat, ParseError};
+
+type Connections<'a> = Vec<(&'a str, Vec<&'a str>)>;
+
+fn component<'a>(input: &str, name: &'a str) -> Result<&'a str, ParseError> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(ParseError::at(input, name));
+    }
+    Ok(name)
+}
+
+pub fn parse_input(input: &str) -> Result<Connections<'_>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (left, right) = line
+                .split_once(": ")
+                .ok_or_else(|| ParseError::at(input, line))?;
+            Ok((
+                component(input, left)?,
+                right
+                    .split(' ')
+                    .map(|name| component(input, name))
+                    .collect::<Result<_, _>>()?,
+            ))
+        })
+        .collect()
+}
+
+/// The product of the sizes of the two groups that cutting three wires separates, or `None` if
+/// no three wires split the components in two.
+pub fn solve_part1(data: &[(&str, Vec<&str>)]) -> Option<usize> {
+    let labels: HashSet<_> = data
+        .iter()
+        .flat_map(|(a, bs)| once(a).chain(bs.iter()).copied())
+        .collect();
+    let mut graph: UnGraph<&str, ()> = UnGraph::new_undirected();
+    let nodes: HashMap<_, _> = labels
+        .iter()
+        .map(|label| (*label, graph.add_node(*label)))
+        .collect();
+    data.iter().for_each(|(start, ends)| {
+        graph.extend_with_edges(ends.iter().map(|end| (nodes[*start], nodes[*end])));
+    });
+    let min_cut_res: Result<Option<(usize, Vec<_>)>, ()> = stoer_wagner_min_cut(&graph, |_| Ok(1));
+
+    let (min_cut, partition) = min_cut_res.ok()??;
+    (min_cut == 3).then(|| partition.len() * (labels.len() - partition.len()))
+}
+
+pub(crate) fn stats(input: &str) -> Result<Vec<Stat>, ParseError> {
+    let data = parse_input(input)?;
+    let components: HashSet<_> = data
+        .iter()
+        .flat_map(|(a, bs)| once(a).chain(bs.iter()).copied())
+        .collect();
+    let connections: usize = data.iter().map(|(_, bs)| bs.len()).sum();
+    Ok(vec![
+        ("components", components.len().to_string()),
+        ("connections", connections.to_string()),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve_part1() {
+        let data = parse_input("a: b c d\nb: c d\nc: d\nd: e\ne: f g h\nf: g h\ng: h").unwrap();
+        assert_eq!(solve_part1(&data), None);
+        let data = parse_input(
+            "a: b c d e f\nb: c d e g\nc: d e h\nd: e\nf: g h i j\ng: h i j\nh: i j\ni: j",
+        )
+        .unwrap();
+        assert_eq!(solve_part1(&data), Some(5 * 5));
+        assert_eq!(solve_part1(&parse_input("").unwrap()), None);
+        assert_eq!(solve_part1(&parse_input("a: b").unwrap()), None);
+    }
+}
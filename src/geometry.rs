@@ -0,0 +1,423 @@
+//! Points, compass directions and axis-aligned boxes shared by the grid and 3D puzzles.
+//!
+//! Grids put the row in `x` and the column in `y`, so that `Dir4::North` decreases `x` and
+//! points order the way the grid reads.
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Index, Mul, Neg, Sub, SubAssign},
+};
+
+/// A point, or a displacement, in the plane.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point, or a displacement, in space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a < b {
+        b - a
+    } else {
+        a - b
+    }
+}
+
+macro_rules! point {
+    ($point:ident, $($axis:tt => $field:ident),+) => {
+        impl<T> $point<T> {
+            pub const fn new($($field: T),+) -> Self {
+                Self { $($field),+ }
+            }
+        }
+
+        impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> $point<T> {
+            /// Sum of the distances along each axis.
+            pub fn manhattan_distance(self, other: Self) -> T {
+                let distances = [$(abs_diff(self.$field, other.$field)),+];
+                distances.into_iter().reduce(|a, b| a + b).unwrap()
+            }
+
+            /// The point whose coordinates are the least of `self` and `other` on each axis.
+            pub fn least(self, other: Self) -> Self {
+                Self { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            /// The point whose coordinates are the greatest of `self` and `other` on each axis.
+            pub fn greatest(self, other: Self) -> Self {
+                Self { $($field: self.$field.max(other.$field)),+ }
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                Self { $($field: self.$field * factor),+ }
+            }
+        }
+
+        /// Coordinates by axis number, `x` being axis 0.
+        impl<T> Index<usize> for $point<T> {
+            type Output = T;
+
+            fn index(&self, axis: usize) -> &T {
+                match axis {
+                    $($axis => &self.$field,)+
+                    _ => panic!("no axis {axis} in {}", stringify!($point)),
+                }
+            }
+        }
+    };
+}
+
+point!(Point2, 0 => x, 1 => y);
+point!(Point3, 0 => x, 1 => y, 2 => z);
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point2<T> {
+    /// The neighbouring point in direction `dir`.
+    pub fn step(self, dir: Dir4) -> Self {
+        self + dir.offset()
+    }
+
+    /// The four orthogonal neighbours, in the order of `Dir4::ALL`.
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+}
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Point2<T> {
+    /// The z coordinate of the cross product of `self` and `other` seen as 3D vectors, positive
+    /// when `other` lies counterclockwise of `self`.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T> Point3<T> {
+    /// Drops the `z` coordinate.
+    pub fn xy(self) -> Point2<T> {
+        Point2::new(self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// One of the four orthogonal directions of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir4 {
+    /// The directions clockwise from north.
+    pub const ALL: [Dir4; 4] = [Dir4::North, Dir4::East, Dir4::South, Dir4::West];
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The direction a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The direction a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// The displacement of a single step in this direction.
+    pub fn offset<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Dir4::North => (-1, 0),
+            Dir4::East => (0, 1),
+            Dir4::South => (1, 0),
+            Dir4::West => (0, -1),
+        };
+        Point2::new(T::from(x), T::from(y))
+    }
+}
+
+/// One of the eight directions of a grid, diagonals included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    /// The directions clockwise from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The direction an eighth of a turn clockwise.
+    pub fn rotate_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The direction an eighth of a turn counterclockwise.
+    pub fn rotate_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// The displacement of a single step in this direction.
+    pub fn offset<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Dir8::North => (-1, 0),
+            Dir8::NorthEast => (-1, 1),
+            Dir8::East => (0, 1),
+            Dir8::SouthEast => (1, 1),
+            Dir8::South => (1, 0),
+            Dir8::SouthWest => (1, -1),
+            Dir8::West => (0, -1),
+            Dir8::NorthWest => (-1, -1),
+        };
+        Point2::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[2 * dir as usize]
+    }
+}
+
+/// The points between two corners of a rectangle, both included.
+///
+/// The rectangle is empty if `min` exceeds `max` on either axis. Its corners are points of a
+/// grid, such as the tiles that bound a map, so a rectangle of one tile has `min == max`; the
+/// sides of a [`crate::interval::IntervalBox`], which is split at thresholds, are half-open
+/// instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+/// The points between two corners of a cuboid, both included.
+///
+/// The cuboid is empty if `min` exceeds `max` on any axis. Both corners are cubes of the box, as
+/// the two ends of a day 22 brick are, unlike the half-open sides of an
+/// [`crate::interval::IntervalBox`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+macro_rules! aligned_box {
+    ($box:ident, $point:ident, $($field:ident),+) => {
+        impl<T> $box<T> {
+            pub const fn new(min: $point<T>, max: $point<T>) -> Self {
+                Self { min, max }
+            }
+        }
+
+        impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> $box<T> {
+            /// The smallest box containing both `a` and `b`, in whatever order.
+            pub fn spanning(a: $point<T>, b: $point<T>) -> Self {
+                Self::new(a.least(b), a.greatest(b))
+            }
+
+            /// The smallest box containing all of `points`, or `None` if there are none.
+            pub fn bounding(points: impl IntoIterator<Item = $point<T>>) -> Option<Self> {
+                points
+                    .into_iter()
+                    .map(|point| Self::new(point, point))
+                    .reduce(|bounds, point| Self::spanning(
+                        bounds.min.least(point.min),
+                        bounds.max.greatest(point.max),
+                    ))
+            }
+
+            pub fn is_empty(&self) -> bool {
+                $(self.min.$field > self.max.$field)||+
+            }
+
+            pub fn contains(&self, point: $point<T>) -> bool {
+                $((self.min.$field..=self.max.$field).contains(&point.$field))&&+
+            }
+
+            /// The points in both `self` and `other`, which may be empty.
+            pub fn intersection(&self, other: &Self) -> Self {
+                Self::new(self.min.greatest(other.min), self.max.least(other.max))
+            }
+
+            pub fn intersects(&self, other: &Self) -> bool {
+                !self.intersection(other).is_empty()
+            }
+        }
+    };
+}
+
+aligned_box!(Rect, Point2, x, y);
+aligned_box!(Cuboid, Point3, x, y, z);
+
+impl<T> Cuboid<T> {
+    /// The shadow of the cuboid on the `xy` plane.
+    pub fn xy(self) -> Rect<T> {
+        Rect::new(self.min.xy(), self.max.xy())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point2::new(3i64, -2);
+        let b = Point2::new(-1, 5);
+        assert_eq!(a + b, Point2::new(2, 3));
+        assert_eq!(a - b, Point2::new(4, -7));
+        assert_eq!(-a * 2, Point2::new(-6, 4));
+        assert_eq!(a.manhattan_distance(b), 11);
+        assert_eq!(Point2::new(1i64, 0).cross(Point2::new(0, 1)), 1);
+        let p = Point3::new(1u8, 7, 3);
+        assert_eq!(p.manhattan_distance(Point3::new(4, 2, 3)), 8);
+        assert_eq!((p[0], p[1], p[2]), (1, 7, 3));
+        assert_eq!(p.xy(), Point2::new(1, 7));
+        assert_eq!(p.to_string(), "(1, 7, 3)");
+    }
+
+    #[test]
+    fn test_dir4() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_eq!(
+                dir.offset::<i16>() + dir.opposite().offset(),
+                Point2::default()
+            );
+        }
+        assert_eq!(Dir4::North.turn_right(), Dir4::East);
+        assert_eq!(Dir4::North.turn_left(), Dir4::West);
+        let neighbours: Vec<_> = Point2::new(0i16, 0).neighbours().collect();
+        assert_eq!(
+            neighbours,
+            [
+                Point2::new(-1, 0),
+                Point2::new(0, 1),
+                Point2::new(1, 0),
+                Point2::new(0, -1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dir8() {
+        for dir in Dir8::ALL {
+            assert_eq!(dir.rotate_left().rotate_right(), dir);
+            assert_eq!(
+                dir.offset::<i64>() + dir.opposite().offset(),
+                Point2::default()
+            );
+        }
+        assert_eq!(Dir8::NorthWest.rotate_right(), Dir8::North);
+        for dir in Dir4::ALL {
+            assert_eq!(Dir8::from(dir).offset::<i8>(), dir.offset());
+            assert_eq!(
+                Dir8::from(dir).rotate_right().rotate_right(),
+                Dir8::from(dir.turn_right())
+            );
+        }
+    }
+
+    #[test]
+    fn test_boxes() {
+        let rect = Rect::spanning(Point2::new(4i16, 0), Point2::new(0, 2));
+        assert_eq!(rect, Rect::new(Point2::new(0, 0), Point2::new(4, 2)));
+        assert!(rect.contains(Point2::new(4, 2)));
+        assert!(!rect.contains(Point2::new(5, 2)));
+        assert!(Rect::new(Point2::new(0, 0), Point2::new(-1, 3)).is_empty());
+        assert_eq!(
+            Rect::bounding([Point2::new(1, 5), Point2::new(3, -2), Point2::new(2, 0)]),
+            Some(Rect::new(Point2::new(1, -2), Point2::new(3, 5)))
+        );
+        let a = Cuboid::new(Point3::new(0, 0, 0), Point3::new(2, 2, 2));
+        let b = Cuboid::new(Point3::new(2, 1, 0), Point3::new(3, 1, 5));
+        let c = Cuboid::new(Point3::new(0, 3, 0), Point3::new(0, 3, 0));
+        assert_eq!(
+            a.intersection(&b),
+            Cuboid::new(Point3::new(2, 1, 0), Point3::new(2, 1, 2))
+        );
+        assert!(!a.intersects(&c));
+        assert!(a.xy().intersects(&b.xy()));
+    }
+}
@@ -0,0 +1,13 @@
+//! Hash maps and sets for the small keys that the solvers' inner loops hash.
+//!
+//! They use the standard SipHash by default and the faster, non-cryptographic FxHash with the
+//! `fast-hash` feature. Either way they are built with `default()`, `from_iter()` or
+//! `collect()` rather than `new()`.
+
+#[cfg(feature = "fast-hash")]
+pub(crate) type BuildHasher = rustc_hash::FxBuildHasher;
+#[cfg(not(feature = "fast-hash"))]
+pub(crate) type BuildHasher = std::hash::RandomState;
+
+pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasher>;
+pub(crate) type HashSet<T> = std::collections::HashSet<T, BuildHasher>;
@@ -0,0 +1,67 @@
+use std::panic;
+
+use crate::runner::PARSERS;
+
+/// How well an input matches the format of a day's puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub day: u8,
+    /// Number of bytes of the input accepted before parsing failed, or its whole length.
+    pub consumed: usize,
+    pub parsed: bool,
+}
+
+/// Runs the parser of every day on `input` and ranks the days by how much of it they accept,
+/// best first.
+///
+/// A parser that panics counts as rejecting the input outright.
+pub fn identify(input: &str) -> Vec<Candidate> {
+    let mut candidates: Vec<_> = PARSERS
+        .iter()
+        .map(|&(day, parser)| {
+            let (consumed, parsed) = match panic::catch_unwind(|| parser(input)) {
+                Ok(Ok(())) => (input.len(), true),
+                Ok(Err(e)) => (e.offset, false),
+                Err(_) => (0, false),
+            };
+            Candidate {
+                day,
+                consumed,
+                parsed,
+            }
+        })
+        .collect();
+    candidates.sort_by_key(|c| (!c.parsed, usize::MAX - c.consumed, c.day));
+    candidates
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_identify() {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+        let candidates = identify(input);
+        assert_eq!(candidates.len(), PARSERS.len());
+        let parsed: Vec<_> = candidates
+            .iter()
+            .filter(|c| c.parsed)
+            .map(|c| c.day)
+            .collect();
+        // Any text with a digit on every line is a calibration document.
+        assert_eq!(parsed, [1, 15]);
+        assert!(candidates
+            .windows(2)
+            .all(|w| w[0].parsed || w[0].consumed >= w[1].consumed));
+    }
+
+    #[test]
+    fn test_identify_workflows() {
+        let input = "in{s<1351:A,R}\n\n{x=787,m=2655,a=1222,s=2876}\n";
+        assert_eq!(identify(input)[0].day, 19);
+        let truncated = "in{s<1351:A,R}\n\n{x=787,m=2655,a=1222}\n";
+        let best = &identify(truncated)[0];
+        assert_eq!((best.day, best.parsed, best.consumed), (19, false, 36));
+    }
+}
@@ -0,0 +1,293 @@
+//! Half-open ranges of integers, the boxes they span and normalised sets of them, for the puzzles
+//! that push whole ranges of values through a transformation at once.
+
+use std::ops::Sub;
+
+/// The values `v` such that `start <= v < end`, which is empty unless `start < end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values in both `self` and `other`, which may be empty.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The values in either `self` or `other`, or `None` if they are neither overlapping nor
+    /// adjacent, so that a single interval cannot hold them.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        (self.start <= other.end && other.start <= self.end)
+            .then(|| Self::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// The values of `self` below and above `other`, either of which may be empty.
+    pub fn difference(&self, other: &Self) -> (Self, Self) {
+        if other.is_empty() {
+            return (*self, Self::new(self.end, self.end));
+        }
+        let (below, rest) = self.split_at(other.start);
+        (below, rest.split_at(other.end).1)
+    }
+
+    /// The values below `threshold` and those at or above it, either of which may be empty.
+    pub fn split_at(&self, threshold: T) -> (Self, Self) {
+        let mid = threshold.clamp(self.start, self.end.max(self.start));
+        (Self::new(self.start, mid), Self::new(mid, self.end))
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Default> Interval<T> {
+    /// The number of values, zero for an empty interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+/// The points whose `i`-th coordinate lies in `sides[i]` for every axis `i`.
+///
+/// Each side is a half-open [`Interval`], so that splitting the box at a threshold, as day 19
+/// does with the ratings a rule accepts, leaves halves that neither overlap nor miss a point.
+/// The boxes of [`crate::geometry`], which enclose grid cells, include both their corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntervalBox<T, const N: usize> {
+    pub sides: [Interval<T>; N],
+}
+
+impl<T: Copy + Ord, const N: usize> IntervalBox<T, N> {
+    pub const fn new(sides: [Interval<T>; N]) -> Self {
+        Self { sides }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sides.iter().any(Interval::is_empty)
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.sides
+            .iter()
+            .zip(point)
+            .all(|(side, value)| side.contains(value))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(std::array::from_fn(|axis| {
+            self.sides[axis].intersection(&other.sides[axis])
+        }))
+    }
+
+    /// The same box with its side along `axis` replaced by `side`.
+    pub fn with_side(&self, axis: usize, side: Interval<T>) -> Self {
+        let mut res = *self;
+        res.sides[axis] = side;
+        res
+    }
+
+    /// The points whose coordinate along `axis` is below `threshold` and those where it is at or
+    /// above it.
+    pub fn split_at(&self, axis: usize, threshold: T) -> (Self, Self) {
+        let (below, above) = self.sides[axis].split_at(threshold);
+        (self.with_side(axis, below), self.with_side(axis, above))
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Default + Into<u64>, const N: usize> IntervalBox<T, N> {
+    /// The number of points, zero for an empty box.
+    pub fn volume(&self) -> u64 {
+        self.sides.iter().map(|side| side.len().into()).product()
+    }
+}
+
+/// A set of values held as the fewest possible intervals, in increasing order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<_> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_unstable_by_key(|i| i.start);
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut().and_then(|last| last.union(&interval)) {
+                Some(union) => *merged.last_mut().unwrap() = union,
+                None => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::from_iter([interval])
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The disjoint, non-adjacent intervals that make up the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(index).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut res = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            res.push(a.intersection(b));
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        res.into_iter().collect()
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut res = Vec::new();
+        for &interval in &self.intervals {
+            let mut rest = interval;
+            for removed in &other.intervals {
+                let (below, above) = rest.difference(removed);
+                res.push(below);
+                rest = above;
+            }
+            res.push(rest);
+        }
+        res.into_iter().collect()
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Default + Into<u64>> IntervalSet<T> {
+    /// The number of values in the set.
+    pub fn len(&self) -> u64 {
+        self.iter().map(|i| i.len().into()).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2u64, 8);
+        let b = Interval::new(5, 12);
+        assert_eq!(a.len(), 6);
+        assert_eq!(Interval::new(8u64, 2).len(), 0);
+        assert_eq!(a.intersection(&b), Interval::new(5, 8));
+        assert!(a.intersection(&Interval::new(8, 9)).is_empty());
+        assert_eq!(a.union(&b), Some(Interval::new(2, 12)));
+        assert_eq!(a.union(&Interval::new(8, 9)), Some(Interval::new(2, 9)));
+        assert_eq!(a.union(&Interval::new(9, 10)), None);
+        assert_eq!(
+            a.difference(&Interval::new(4, 6)),
+            (Interval::new(2, 4), Interval::new(6, 8))
+        );
+        let (below, above) = a.difference(&b);
+        assert_eq!(below, Interval::new(2, 5));
+        assert!(above.is_empty());
+        assert_eq!(a.split_at(0), (Interval::new(2, 2), Interval::new(2, 8)));
+        assert_eq!(a.split_at(3), (Interval::new(2, 3), Interval::new(3, 8)));
+    }
+
+    #[test]
+    fn test_interval_box() {
+        let cube = IntervalBox::new([Interval::new(1u16, 11); 3]);
+        assert_eq!(cube.volume(), 1000);
+        let (below, above) = cube.split_at(1, 4);
+        assert_eq!((below.volume(), above.volume()), (300, 700));
+        assert!(above.contains([1, 4, 10]));
+        assert!(!above.contains([1, 3, 10]));
+        assert!(below.intersection(&above).is_empty());
+        assert_eq!(below.intersection(&above).volume(), 0);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let set: IntervalSet<u64> = [
+            Interval::new(10, 20),
+            Interval::new(0, 5),
+            Interval::new(5, 7),
+            Interval::new(15, 25),
+            Interval::new(30, 30),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [Interval::new(0, 7), Interval::new(10, 25)]
+        );
+        assert_eq!((set.len(), set.min()), (22, Some(0)));
+        assert!(set.contains(24) && !set.contains(8) && !set.contains(25));
+        let other = IntervalSet::from_iter([Interval::new(3, 12), Interval::new(20, 40)]);
+        assert_eq!(
+            set.intersection(&other).iter().collect::<Vec<_>>(),
+            [
+                Interval::new(3, 7),
+                Interval::new(10, 12),
+                Interval::new(20, 25)
+            ]
+        );
+        assert_eq!(
+            set.difference(&other).iter().collect::<Vec<_>>(),
+            [Interval::new(0, 3), Interval::new(12, 20)]
+        );
+        assert_eq!(
+            set.union(&other).iter().collect::<Vec<_>>(),
+            [Interval::new(0, 40)]
+        );
+        assert!(IntervalSet::<u64>::default().min().is_none());
+    }
+}
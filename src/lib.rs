@@ -0,0 +1,54 @@
+//! Solutions to the Advent of Code 2023 puzzles.
+//!
+//! Each `dayNN` module exposes a fallible `parse_input` together with the solvers of both
+//! parts; the binaries in `src/bin` only feed them the puzzle inputs, and `aoc` bundles the
+//! tooling built on top of them.
+
+#[macro_use]
+mod arith;
+
+pub mod answer;
+pub mod budget;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub mod geometry;
+mod hash;
+pub mod identify;
+pub mod interval;
+pub mod math;
+mod parse;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod serve;
+pub mod stats;
+#[cfg(target_os = "linux")]
+pub mod watch;
+
+pub use answer::Answer;
+pub use parse::ParseError;
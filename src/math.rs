@@ -0,0 +1,180 @@
+//! Number theory shared by the puzzles that reason about cycles and closed forms.
+
+/// Greatest common divisor, with `gcd(0, 0) == 0`.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or `None` if it does not fit in a `u64`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Returns `(g, x, y)` such that `g` is the non-negative gcd of `a` and `b` and
+/// `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Solves the system `t ≡ residue (mod modulus)` over all `congruences`, whose moduli need not
+/// be coprime.
+///
+/// Returns `(r, m)` such that the solutions are exactly the `t ≡ r (mod m)`, where `m` is the
+/// lcm of the moduli, or `None` if the congruences are inconsistent, a modulus is zero or `m`
+/// does not fit in a `u64`.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1): (u64, u64), (r2, m2)| {
+            if m2 == 0 {
+                return None;
+            }
+            let r2 = r2 % m2;
+            let (g, p, _) = extended_gcd(m1.into(), m2.into());
+            let diff = i128::from(r2) - i128::from(r1);
+            if diff % g != 0 {
+                return None;
+            }
+            let m = lcm(m1, m2)?;
+            // t = r1 + m1 * k where m1 * k ≡ diff (mod m2), i.e. k ≡ (diff / g) * p (mod m2 / g).
+            let step = m2 as i128 / g;
+            let k = (diff / g).rem_euclid(step) as u128 * p.rem_euclid(step) as u128 % step as u128;
+            let t = u128::from(r1) + u128::from(m1) * k;
+            Some(((t % u128::from(m)) as u64, m))
+        })
+}
+
+/// Smallest `t >= floor` in the residue class `r (mod m)`, or `None` if it does not fit in a
+/// `u64`.
+pub fn least_at_or_above((r, m): (u64, u64), floor: u64) -> Option<u64> {
+    if r >= floor {
+        return Some(r);
+    }
+    (floor - r).div_ceil(m).checked_mul(m)?.checked_add(r)
+}
+
+/// Largest integer whose square does not exceed `n`.
+pub fn isqrt(n: u128) -> u64 {
+    if n < 2 {
+        return n as u64;
+    }
+    // Newton's iteration decreases monotonically towards the root from any overestimate.
+    let mut x = n;
+    let mut y = n.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    // The root of any u128 fits in a u64.
+    x as u64
+}
+
+/// Binomial coefficient `n` choose `k`, or `None` if it does not fit in a `u64`.
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    // After step `i` the product is `n` choose `i + 1`, which `i + 1` divides out of
+    // `acc * (n - i)` exactly: whatever part of it `acc` does not absorb divides `n - i`.
+    (0..k.min(n - k)).try_fold(1, |acc: u64, i| {
+        let g = gcd(acc, i + 1);
+        (acc / g).checked_mul((n - i) / ((i + 1) / g))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(1 << 40, (1 << 40) - 1), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(
+                g,
+                gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64) as i128
+            );
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(0, 4), (2, 6)]), Some((8, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(3, 0)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        let (big, bigger) = (u32::MAX as u64, u32::MAX as u64 - 2);
+        assert_eq!(crt([(5, big), (5, bigger)]), Some((5, big * bigger)));
+    }
+
+    #[test]
+    fn test_least_at_or_above() {
+        assert_eq!(least_at_or_above((2, 5), 0), Some(2));
+        assert_eq!(least_at_or_above((2, 5), 3), Some(7));
+        assert_eq!(least_at_or_above((0, 5), 5), Some(5));
+        assert_eq!(least_at_or_above((0, 7), u64::MAX), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..1000 {
+            let r = u128::from(isqrt(n));
+            assert!(r * r <= n && (r + 1) * (r + 1) > n);
+        }
+        assert_eq!(isqrt(u64::MAX.into()), u32::MAX as u64);
+        assert_eq!(isqrt(u128::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial(6, 0), Some(1));
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(binomial(6, 2), Some(15));
+        assert_eq!(binomial(6, 6), Some(1));
+        assert_eq!(binomial(6, 7), Some(0));
+        assert_eq!(binomial(u64::MAX, 1), Some(u64::MAX));
+        assert_eq!(binomial(u64::MAX, u64::MAX - 1), Some(u64::MAX));
+        assert_eq!(binomial(u64::MAX, 2), None);
+        assert_eq!(binomial(67, 33), Some(14_226_520_737_620_288_370));
+        assert_eq!(binomial(68, 34), None);
+        for n in 0..20 {
+            for k in 1..=n {
+                assert_eq!(
+                    binomial(n, k),
+                    Some(binomial(n - 1, k - 1).unwrap() + binomial(n - 1, k).unwrap())
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use nom::IResult;
+
+/// Error returned by the `parse_input` functions when an input does not follow the puzzle format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset in the input where parsing failed.
+    pub offset: usize,
+}
+
+impl ParseError {
+    /// Locates the failure at the start of `rest`, or at the end of `input` if `rest` is not
+    /// one of its subslices.
+    pub fn at(input: &str, rest: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        let position = rest.as_ptr() as usize;
+        let offset = if (start..=start + input.len()).contains(&position) {
+            position - start
+        } else {
+            input.len()
+        };
+        Self { offset }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid input at byte {}", self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Extracts the output of a nom parser run on `input`, which must have consumed everything but
+/// trailing whitespace.
+pub fn finish<'a, O>(input: &'a str, result: IResult<&'a str, O>) -> Result<O, ParseError> {
+    match result {
+        Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+        Ok((rest, _)) => Err(ParseError::at(input, rest)),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::at(input, e.input)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError {
+            offset: input.len(),
+        }),
+    }
+}
+
+/// Parses a non-degenerate rectangular grid with one row per line, mapping each character
+/// through `tile`.
+pub fn grid<T>(input: &str, tile: impl Fn(char) -> Option<T>) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut width = None;
+    input
+        .lines()
+        .map(|line| {
+            let row = line
+                .char_indices()
+                .map(|(j, c)| tile(c).ok_or_else(|| ParseError::at(input, &line[j..])))
+                .collect::<Result<Vec<_>, _>>()?;
+            if row.is_empty() || *width.get_or_insert(row.len()) != row.len() {
+                return Err(ParseError::at(input, line));
+            }
+            Ok(row)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_at() {
+        let input = "abc\ndef";
+        assert_eq!(ParseError::at(input, &input[4..]).offset, 4);
+        assert_eq!(ParseError::at(input, &input[7..]).offset, 7);
+        assert_eq!(ParseError::at(input, "elsewhere").offset, 7);
+    }
+
+    #[test]
+    fn test_finish() {
+        let parse = |input| finish(input, nom::character::complete::digit1(input));
+        assert_eq!(parse("123\n"), Ok("123"));
+        assert_eq!(parse("123x"), Err(ParseError { offset: 3 }));
+        assert_eq!(parse("x"), Err(ParseError { offset: 0 }));
+    }
+
+    #[test]
+    fn test_grid() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(grid("12\n34", digit), Ok(vec![vec![1, 2], vec![3, 4]]));
+        assert_eq!(grid("12\n3x", digit), Err(ParseError { offset: 4 }));
+        assert_eq!(grid("12\n345", digit), Err(ParseError { offset: 3 }));
+        assert_eq!(grid("\n", digit), Err(ParseError { offset: 0 }));
+    }
+}
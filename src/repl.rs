@@ -0,0 +1,128 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+use crate::{day05, day15, day17, day19, ParseError};
+
+/// Per-day queries on a parsed puzzle input.
+pub trait Explore {
+    /// Usage and description of every query understood by [`Explore::query`].
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Answers the query `command` with its (unparsed) arguments `args`.
+    fn query(&self, command: &str, args: &str) -> Result<String, String>;
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Unsupported(u8),
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unsupported(day) => write!(f, "day {day} has no queries"),
+            Error::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// Parses `input` as the puzzle input of `day` and wraps it in that day's queries.
+pub fn explorer(day: u8, input: &str) -> Result<Box<dyn Explore + '_>, Error> {
+    Ok(match day {
+        5 => Box::new(day05::Explorer::new(input)?),
+        15 => Box::new(day15::Explorer::new(input)?),
+        17 => Box::new(day17::Explorer::new(input)?),
+        19 => Box::new(day19::Explorer::new(input)?),
+        _ => return Err(Error::Unsupported(day)),
+    })
+}
+
+/// Answers the queries read line by line from `reader` until it is exhausted or `quit` is
+/// entered.
+pub fn run(explorer: &dyn Explore, reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+    let mut lines = reader.lines();
+    loop {
+        write!(writer, "> ")?;
+        writer.flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            return writeln!(writer);
+        };
+        let (command, args) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        match command {
+            "" => {}
+            "quit" | "exit" => return Ok(()),
+            "help" => {
+                for (usage, description) in explorer
+                    .commands()
+                    .iter()
+                    .chain(&[("help", "list the queries"), ("quit", "leave the REPL")])
+                {
+                    writeln!(writer, "{usage:<24}{description}")?;
+                }
+            }
+            _ => match explorer.query(command, args.trim()) {
+                Ok(answer) => writeln!(writer, "{answer}")?,
+                Err(e) => writeln!(writer, "error: {e}")?,
+            },
+        }
+    }
+}
+
+/// Parses the whitespace-separated arguments of a query.
+pub(crate) fn arguments<T: std::str::FromStr, const N: usize>(
+    args: &str,
+) -> Result<[T; N], String> {
+    let values = args
+        .split_whitespace()
+        .map(|arg| arg.parse().map_err(|_| format!("invalid argument `{arg}`")))
+        .collect::<Result<Vec<_>, _>>()?;
+    values
+        .try_into()
+        .map_err(|values: Vec<T>| format!("expected {N} arguments, got {}", values.len()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Echo;
+
+    impl Explore for Echo {
+        fn commands(&self) -> &'static [(&'static str, &'static str)] {
+            &[("add <a> <b>", "add two numbers")]
+        }
+
+        fn query(&self, command: &str, args: &str) -> Result<String, String> {
+            match command {
+                "add" => arguments::<u32, 2>(args).map(|[a, b]| (a + b).to_string()),
+                _ => Err(format!("unknown query `{command}`")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let mut output = vec![];
+        run(
+            &Echo,
+            "add 1 2\n\nadd 1\nmul 2 3\nquit\nadd 3 4\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "> 3\n> > error: expected 2 arguments, got 1\n> error: unknown query `mul`\n> "
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    fmt::Write,
+    panic,
+    time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+
+use crate::{
+    budget::Budget,
+    day10, day20, day21, day23,
+    runner::{self, Solver},
+    Answer, ParseError,
+};
+
+/// An assumption a solver makes about its input beyond the puzzle statement, and whether the
+/// input satisfies it.
+pub type Check = (&'static str, bool);
+
+/// A grid drawn one character per tile, with the tiles of interest highlighted.
+pub type Rendering = Vec<Vec<(char, bool)>>;
+
+/// The outcome of solving the input of a day.
+#[derive(Debug)]
+pub struct Entry {
+    pub day: u8,
+    /// The answer to each part, or why it could not be computed.
+    pub answers: Vec<Result<Answer, String>>,
+    pub elapsed: Duration,
+    pub checks: Vec<Check>,
+    pub rendering: Option<Rendering>,
+}
+
+fn checks(day: u8, input: &str) -> Result<Vec<Check>, ParseError> {
+    match day {
+        20 => day20::checks(input),
+        21 => day21::checks(input),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn rendering(day: u8, input: &str, budget: &mut Budget) -> Result<Option<Rendering>, ParseError> {
+    Ok(match day {
+        10 => Some(day10::render(input)?),
+        23 => Some(day23::render(input, budget)?),
+        _ => None,
+    })
+}
+
+/// Solves each part of `input` with `solvers` within `budget`, timing them, then checks the
+/// assumptions of the solvers and draws the input if `day` supports it, with searches bounded by
+/// the same budget.
+///
+/// Inputs that fail to parse, or make the checks or drawing panic, have neither.
+pub fn entry(day: u8, solvers: &[Solver], input: &str, mut budget: Budget) -> Entry {
+    let start = Instant::now();
+    let answers = solvers
+        .iter()
+        .map(|&solver| {
+            runner::solve_within(solver, input, budget.clone()).map_err(|e| e.to_string())
+        })
+        .collect();
+    let elapsed = start.elapsed();
+    Entry {
+        day,
+        answers,
+        elapsed,
+        checks: panic::catch_unwind(|| checks(day, input))
+            .ok()
+            .and_then(Result::ok)
+            .unwrap_or_default(),
+        rendering: panic::catch_unwind(move || rendering(day, input, &mut budget))
+            .ok()
+            .and_then(Result::ok)
+            .flatten(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                c => escaped.push(c),
+            }
+            escaped
+        })
+}
+
+/// Draws `rendering` as preformatted text, each run of highlighted tiles in a `<b>` element.
+fn draw(rendering: &Rendering) -> String {
+    let mut html = String::from("<pre>");
+    for row in rendering {
+        for (highlighted, run) in &row.iter().group_by(|(_, highlighted)| *highlighted) {
+            let run: String = run.map(|&(c, _)| c).collect();
+            if highlighted {
+                write!(html, "<b>{}</b>", escape(&run)).unwrap();
+            } else {
+                html.push_str(&escape(&run));
+            }
+        }
+        html.push('\n');
+    }
+    html.push_str("</pre>");
+    html
+}
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; vertical-align: top; }
+td.answer { font-family: monospace; }
+td.time { text-align: right; }
+.error { color: #b00; }
+.pass { color: #070; }
+.fail { color: #b00; }
+ul { margin: 0; padding-left: 1.2em; }
+pre { font-size: 6px; line-height: 6px; color: #999; }
+pre b { color: #000; }";
+
+/// Lays out `entries` as a single HTML page that depends on no other file.
+pub fn html(title: &str, entries: &[Entry]) -> String {
+    let title = escape(title);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n\
+         <table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Time</th><th>Checks</th></tr>\n"
+    );
+    for entry in entries {
+        let day = if entry.rendering.is_some() {
+            format!("<a href=\"#day{:02}\">{}</a>", entry.day, entry.day)
+        } else {
+            entry.day.to_string()
+        };
+        let answers = match &entry.answers[..] {
+            [Err(e), others @ ..] if others.iter().all(|other| other.as_ref().err() == Some(e)) => {
+                format!("<td colspan=\"2\" class=\"error\">{}</td>", escape(e))
+            }
+            answers => (0..2)
+                .map(|part| match answers.get(part) {
+                    Some(Ok(answer)) => {
+                        format!("<td class=\"answer\">{}</td>", escape(&answer.to_string()))
+                    }
+                    Some(Err(e)) => format!("<td class=\"error\">{}</td>", escape(e)),
+                    None => "<td class=\"answer\"></td>".to_string(),
+                })
+                .collect(),
+        };
+        let checks: String = entry
+            .checks
+            .iter()
+            .map(|&(assumption, holds)| {
+                let (class, mark) = if holds {
+                    ("pass", "✔")
+                } else {
+                    ("fail", "✘")
+                };
+                format!("<li class=\"{class}\">{mark} {}</li>", escape(assumption))
+            })
+            .collect();
+        writeln!(
+            html,
+            "<tr><td>{day}</td>{answers}<td class=\"time\">{:.2?}</td><td><ul>{checks}</ul></td></tr>",
+            entry.elapsed
+        )
+        .unwrap();
+    }
+    let total: Duration = entries.iter().map(|entry| entry.elapsed).sum();
+    writeln!(
+        html,
+        "<tr><th colspan=\"3\">Total</th><td class=\"time\">{total:.2?}</td><td></td></tr>\n</table>"
+    )
+    .unwrap();
+    for entry in entries {
+        if let Some(rendering) = &entry.rendering {
+            writeln!(
+                html,
+                "<h2 id=\"day{:02}\">Day {}</h2>\n{}",
+                entry.day,
+                entry.day,
+                draw(rendering)
+            )
+            .unwrap();
+        }
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_entry() {
+        let solvers = runner::solvers(15).unwrap();
+        let entry = entry(15, solvers, "rn=1,cm-\n", Budget::unlimited());
+        assert_eq!(
+            entry.answers,
+            [Ok(Answer::from(283u64)), Ok(Answer::from(1u64))]
+        );
+        assert!(entry.checks.is_empty());
+        assert!(entry.rendering.is_none());
+        let entry = super::entry(15, solvers, "rn=1,HASH\n", Budget::unlimited());
+        assert_eq!(
+            entry.answers,
+            [
+                Err("invalid input at byte 5".to_string()),
+                Err("invalid input at byte 5".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let entries = [
+            Entry {
+                day: 10,
+                answers: vec![Ok(Answer::from(4u64)), Ok(Answer::from(1u64))],
+                elapsed: Duration::from_millis(3),
+                checks: vec![("the loop <is> closed", true)],
+                rendering: Some(vec![vec![('┌', true), ('─', true), ('.', false)]]),
+            },
+            Entry {
+                day: 20,
+                answers: vec![
+                    Err("the solver panicked".to_string()),
+                    Err("the solver panicked".to_string()),
+                ],
+                elapsed: Duration::from_millis(1),
+                checks: vec![("rx is fed by a single conjunction", false)],
+                rendering: None,
+            },
+            Entry {
+                day: 21,
+                answers: vec![Ok(Answer::from(2u64)), Err("no solution".to_string())],
+                elapsed: Duration::from_millis(1),
+                checks: Vec::new(),
+                rendering: None,
+            },
+        ];
+        let html = html("Report & co", &entries);
+        assert!(html.contains("<title>Report &amp; co</title>"));
+        assert!(html.contains("<td class=\"answer\">4</td><td class=\"answer\">1</td>"));
+        assert!(html.contains("<li class=\"pass\">✔ the loop &lt;is&gt; closed</li>"));
+        assert!(html.contains("<td colspan=\"2\" class=\"error\">the solver panicked</td>"));
+        assert!(html.contains("<td class=\"answer\">2</td><td class=\"error\">no solution</td>"));
+        assert!(html.contains("<a href=\"#day10\">10</a>"));
+        assert!(html.contains("<h2 id=\"day10\">Day 10</h2>\n<pre><b>┌─</b>.\n</pre>"));
+        assert!(html.contains("<td class=\"time\">5.00ms</td>"));
+    }
+}
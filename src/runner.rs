@@ -0,0 +1,492 @@
+use std::{fmt, panic};
+
+use crate::{
+    budget::{Budget, Timeout},
+    day01::CalibrationError,
+    Answer, ParseError,
+};
+
+/// Parses a puzzle input and returns the answer to one of its parts, within a budget that only
+/// the solvers prone to running for very long spend.
+pub type Solver = fn(&str, &mut Budget) -> Result<Answer, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(ParseError),
+    NoSolution,
+    /// A day 1 document with a line the calibration cannot read.
+    Calibration(CalibrationError),
+    Timeout(Timeout),
+    Panicked,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(e) => e.fmt(f),
+            Error::NoSolution => write!(f, "the input has no solution"),
+            Error::Calibration(e) => write!(f, "the input has no solution: {e}"),
+            Error::Timeout(e) => e.fmt(f),
+            Error::Panicked => write!(f, "the solver panicked"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<CalibrationError> for Error {
+    fn from(e: CalibrationError) -> Self {
+        Error::Calibration(e)
+    }
+}
+
+impl From<Timeout> for Error {
+    fn from(e: Timeout) -> Self {
+        Error::Timeout(e)
+    }
+}
+
+macro_rules! answer {
+    ($answer:expr) => {
+        Ok(Answer::from($answer))
+    };
+}
+
+/// Checks whether an input follows the format of a day's puzzle, without solving it.
+pub type Parser = fn(&str) -> Result<(), ParseError>;
+
+/// The parser of every day, in order. `aoc new` appends the days it creates.
+pub const PARSERS: &[(u8, Parser)] = &[
+    (1, crate::day01::check_format),
+    (2, |input| crate::day02::parse_input(input).map(drop)),
+    (3, |input| crate::day03::parse_input(input).map(drop)),
+    (4, |input| crate::day04::parse_input(input).map(drop)),
+    (5, |input| crate::day05::parse_input(input).map(drop)),
+    (6, |input| crate::day06::parse_input(input).map(drop)),
+    (7, |input| crate::day07::parse_input(input).map(drop)),
+    (8, |input| crate::day08::parse_input(input).map(drop)),
+    (9, |input| crate::day09::parse_input(input).map(drop)),
+    (10, |input| crate::day10::parse_input(input).map(drop)),
+    (11, |input| crate::day11::parse_input(input).map(drop)),
+    (12, |input| crate::day12::parse_input(input).map(drop)),
+    (13, |input| crate::day13::parse_input(input).map(drop)),
+    (14, |input| crate::day14::parse_input(input).map(drop)),
+    (15, |input| crate::day15::parse_input(input).map(drop)),
+    (16, |input| crate::day16::parse_input(input).map(drop)),
+    (17, |input| crate::day17::parse_input(input).map(drop)),
+    (18, |input| crate::day18::parse_input(input).map(drop)),
+    (19, |input| crate::day19::parse_input(input).map(drop)),
+    (20, |input| crate::day20::parse_input(input).map(drop)),
+    (21, |input| crate::day21::parse_input(input).map(drop)),
+    (22, |input| crate::day22::parse_input(input).map(drop)),
+    (23, |input| crate::day23::parse_input(input).map(drop)),
+    (24, |input| crate::day24::parse_input(input).map(drop)),
+    (25, |input| crate::day25::parse_input(input).map(drop)),
+];
+
+/// The solvers of the parts of every day, in order. `aoc new` appends the days it creates.
+pub const SOLVERS: &[(u8, &[Solver])] = &[
+    (
+        1,
+        &[
+            |input, _| {
+                use crate::day01::*;
+                let data = parse_input(input)?;
+                answer!(solve_part1(&data, Policy::Fail)?)
+            },
+            |input, _| {
+                use crate::day01::*;
+                let data = parse_input(input)?;
+                answer!(solve_part2(&data, Policy::Fail)?)
+            },
+        ],
+    ),
+    (
+        2,
+        &[
+            |input, _| {
+                use crate::day02::*;
+                let games = parse_input(input)?;
+                answer!(solve_part1(&games, &puzzle_bag()))
+            },
+            |input, _| {
+                use crate::day02::*;
+                let games = parse_input(input)?;
+                answer!(solve_part2(&games).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        3,
+        &[
+            |input, _| {
+                use crate::day03::*;
+                let schematic = parse_input(input)?;
+                answer!(solve_part1(&schematic))
+            },
+            |input, _| {
+                use crate::day03::*;
+                let schematic = parse_input(input)?;
+                answer!(solve_part2(&schematic).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        4,
+        &[
+            |input, _| {
+                use crate::day04::*;
+                let data = parse_input(input)?;
+                answer!(solve_part1(&data).ok_or(Error::NoSolution)?)
+            },
+            |input, _| {
+                use crate::day04::*;
+                let data = parse_input(input)?;
+                answer!(solve_part2(&data).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        5,
+        &[
+            |input, _| {
+                use crate::day05::*;
+                let (seeds, mappings) = parse_input(input)?;
+                answer!(solve_part1(&seeds, &mappings))
+            },
+            |input, _| {
+                use crate::day05::*;
+                let (seeds, mappings) = parse_input(input)?;
+                let seeds = into_intervals(&seeds).ok_or(Error::NoSolution)?;
+                answer!(solve_part2(seeds, &mappings).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        6,
+        &[
+            |input, _| {
+                use crate::day06::*;
+                let races = parse_input(input)?;
+                answer!(solve_part1(&races).ok_or(Error::NoSolution)?)
+            },
+            |input, _| {
+                use crate::day06::*;
+                let races = parse_input(input)?;
+                answer!(solve_part2(&races).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        7,
+        &[
+            |input, _| {
+                use crate::day07::*;
+                let hands = parse_input(input)?;
+                answer!(solve_part1(&hands).ok_or(Error::NoSolution)?)
+            },
+            |input, _| {
+                use crate::day07::*;
+                let hands = parse_input(input)?;
+                answer!(solve_part2(&hands).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        8,
+        &[
+            |input, budget| {
+                use crate::day08::*;
+                let (directions, transitions) = parse_input(input)?;
+                answer!(solve_part1(&directions, &transitions, budget)?.ok_or(Error::NoSolution)?)
+            },
+            |input, budget| {
+                use crate::day08::*;
+                let (directions, transitions) = parse_input(input)?;
+                answer!(solve_part2(&directions, &transitions, budget)?.ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        9,
+        &[
+            |input, _| {
+                use crate::day09::*;
+                let values = parse_input(input)?;
+                let (_, answer) = solve(&values).ok_or(Error::NoSolution)?;
+                answer!(answer)
+            },
+            |input, _| {
+                use crate::day09::*;
+                let values = parse_input(input)?;
+                let (answer, _) = solve(&values).ok_or(Error::NoSolution)?;
+                answer!(answer)
+            },
+        ],
+    ),
+    (
+        10,
+        &[
+            |input, _| {
+                use crate::day10::*;
+                let (grid, start) = parse_input(input)?;
+                let circuit = find_circuit(&grid, start).ok_or(Error::NoSolution)?;
+                answer!(solve_part1(&circuit))
+            },
+            |input, _| {
+                use crate::day10::*;
+                let (grid, start) = parse_input(input)?;
+                let circuit = find_circuit(&grid, start).ok_or(Error::NoSolution)?;
+                answer!(solve_part2(&grid, &circuit))
+            },
+        ],
+    ),
+    (
+        11,
+        &[
+            |input, _| {
+                use crate::day11::*;
+                let universe = parse_input(input)?;
+                answer!(solve_part1(&universe))
+            },
+            |input, _| {
+                use crate::day11::*;
+                let universe = parse_input(input)?;
+                answer!(solve_part2(&universe))
+            },
+        ],
+    ),
+    (
+        12,
+        &[
+            |input, _| {
+                use crate::day12::*;
+                let records = parse_input(input)?;
+                answer!(solve(&records, &mut Memoization::default()).ok_or(Error::NoSolution)?)
+            },
+            |input, _| {
+                use crate::day12::*;
+                let records: Vec<_> = parse_input(input)?
+                    .into_iter()
+                    .map(|r| r.unfold())
+                    .collect();
+                answer!(solve(&records, &mut Memoization::default()).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        13,
+        &[
+            |input, _| {
+                use crate::day13::*;
+                let patterns = parse_input(input)?;
+                answer!(solve_part1(&patterns).ok_or(Error::NoSolution)?)
+            },
+            |input, _| {
+                use crate::day13::*;
+                let patterns = parse_input(input)?;
+                answer!(solve_part2(&patterns).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        14,
+        &[
+            |input, _| {
+                use crate::day14::*;
+                let platform = parse_input(input)?;
+                answer!(solve_part1(&platform))
+            },
+            |input, _| {
+                use crate::day14::*;
+                let platform = parse_input(input)?;
+                answer!(solve_part2(&platform))
+            },
+        ],
+    ),
+    (
+        15,
+        &[
+            |input, _| {
+                use crate::day15::*;
+                let data = parse_input(input)?;
+                answer!(solve_part1(&data))
+            },
+            |input, _| {
+                use crate::day15::*;
+                let data = parse_input(input)?;
+                answer!(solve_part2(&data))
+            },
+        ],
+    ),
+    (
+        16,
+        &[
+            |input, _| {
+                use crate::day16::*;
+                let layout = parse_input(input)?;
+                answer!(solve_part1(&layout))
+            },
+            |input, _| {
+                use crate::day16::*;
+                let layout = parse_input(input)?;
+                answer!(solve_part2(&layout))
+            },
+        ],
+    ),
+    (
+        17,
+        &[
+            |input, _| {
+                use crate::day17::*;
+                let city = parse_input(input)?;
+                answer!(solve_part1(&city))
+            },
+            |input, _| {
+                use crate::day17::*;
+                let city = parse_input(input)?;
+                answer!(solve_part2(&city))
+            },
+        ],
+    ),
+    (
+        18,
+        &[
+            |input, _| {
+                use crate::day18::*;
+                let data = parse_input(input)?;
+                answer!(solve_part1(&data).ok_or(Error::NoSolution)?)
+            },
+            |input, _| {
+                use crate::day18::*;
+                let data = parse_input(input)?;
+                answer!(solve_part2(&data).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        19,
+        &[
+            |input, _| {
+                use crate::day19::*;
+                let (workflows, parts) = parse_input(input)?;
+                answer!(solve_part1(&workflows, &parts))
+            },
+            |input, _| {
+                use crate::day19::*;
+                let (workflows, _) = parse_input(input)?;
+                answer!(solve_part2(&workflows))
+            },
+        ],
+    ),
+    (
+        20,
+        &[
+            |input, _| {
+                use crate::day20::*;
+                let network = parse_input(input)?;
+                answer!(solve_part1(&network))
+            },
+            |input, budget| {
+                use crate::day20::*;
+                let network = parse_input(input)?;
+                answer!(solve_part2(&network, budget)?.ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        21,
+        &[
+            |input, _| {
+                use crate::day21::*;
+                let (garden, start) = parse_input(input)?;
+                answer!(solve_part1(&garden, start))
+            },
+            |input, _| {
+                use crate::day21::*;
+                let (garden, start) = parse_input(input)?;
+                answer!(solve_part2(&garden, start).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        22,
+        &[
+            |input, _| {
+                use crate::day22::*;
+                let bricks = parse_input(input)?;
+                answer!(solve_part1(&bricks))
+            },
+            |input, _| {
+                use crate::day22::*;
+                let bricks = parse_input(input)?;
+                answer!(solve_part2(&bricks))
+            },
+        ],
+    ),
+    (
+        23,
+        &[
+            |input, budget| {
+                use crate::day23::*;
+                let terrain = parse_input(input)?;
+                answer!(solve_part1(&terrain, budget)?.ok_or(Error::NoSolution)?)
+            },
+            |input, budget| {
+                use crate::day23::*;
+                let terrain = parse_input(input)?;
+                answer!(solve_part2(&terrain, budget)?.ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        24,
+        &[
+            |input, _| {
+                use crate::day24::*;
+                let hailstones = parse_input(input)?;
+                answer!(solve_part1::<200_000_000_000_000, 400_000_000_000_000>(
+                    &hailstones
+                ))
+            },
+            |input, _| {
+                use crate::day24::*;
+                let hailstones = parse_input(input)?;
+                answer!(solve_part2(&hailstones).ok_or(Error::NoSolution)?)
+            },
+        ],
+    ),
+    (
+        25,
+        &[|input, _| {
+            use crate::day25::*;
+            let data = parse_input(input)?;
+            answer!(solve_part1(&data).ok_or(Error::NoSolution)?)
+        }],
+    ),
+];
+
+/// The solvers of the parts of `day`, in order, if it is solved.
+pub fn solvers(day: u8) -> Option<&'static [Solver]> {
+    SOLVERS
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, solvers)| *solvers)
+}
+
+/// Runs `solver` on `input`, reporting a panic of the solver as an error.
+pub fn solve(solver: Solver, input: &str) -> Result<Answer, Error> {
+    solve_within(solver, input, Budget::unlimited())
+}
+
+/// Runs `solver` on `input` until it is done or has exhausted `budget`, reporting a panic of
+/// the solver as an error.
+pub fn solve_within(solver: Solver, input: &str, mut budget: Budget) -> Result<Answer, Error> {
+    panic::catch_unwind(move || solver(input, &mut budget)).unwrap_or(Err(Error::Panicked))
+}
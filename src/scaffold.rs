@@ -0,0 +1,256 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The year of the puzzles this crate solves, the only one whose days `aoc new` adds to it.
+pub const YEAR: u16 = 2023;
+
+const MODULE: &str = r#"//! <https://adventofcode.com/{year}/day/{day}>
+
+use nom::{
+    character::complete::{char, newline, one_of},
+    combinator::{map_res, recognize},
+    multi::{many0, many1, separated_list1},
+    sequence::terminated,
+    IResult,
+};
+
+use crate::{parse::finish, ParseError};
+
+fn integer(input: &str) -> IResult<&str, u64> {
+    map_res(
+        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    finish(input, separated_list1(newline, integer)(input))
+}
+
+pub fn solve_part1(data: &[u64]) -> u64 {
+    todo!("{} values", data.len())
+}
+
+pub fn solve_part2(data: &[u64]) -> u64 {
+    todo!("{} values", data.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_input, solve_part1, solve_part2};
+
+    const INPUT: &str = include_str!("../data/examples/day{dd}.txt");
+
+    #[test]
+    #[ignore = "the example and its answer are still to be filled in"]
+    fn test_solve_part1() {
+        let data = parse_input(INPUT).unwrap();
+        assert_eq!(solve_part1(&data), 0);
+    }
+
+    #[test]
+    #[ignore = "the example and its answer are still to be filled in"]
+    fn test_solve_part2() {
+        let data = parse_input(INPUT).unwrap();
+        assert_eq!(solve_part2(&data), 0);
+    }
+}
+"#;
+
+const BINARY: &str = r#"use {crate}::day{dd}::{parse_input, solve_part1, solve_part2};
+
+fn main() {
+    let input = include_str!("../../data/day{dd}.txt");
+    let data = parse_input(input).unwrap();
+    let answer1 = solve_part1(&data);
+    println!("The answer to part 1 is {}", answer1);
+    let answer2 = solve_part2(&data);
+    println!("The answer to part 2 is {}", answer2);
+}
+"#;
+
+const PARSER: &str = r#"    ({day}, |input| crate::day{dd}::parse_input(input).map(drop)),
+"#;
+
+const SOLVER: &str = r#"    (
+        {day},
+        &[
+            |input, _| {
+                use crate::day{dd}::*;
+                let data = parse_input(input)?;
+                answer!(solve_part1(&data))
+            },
+            |input, _| {
+                use crate::day{dd}::*;
+                let data = parse_input(input)?;
+                answer!(solve_part2(&data))
+            },
+        ],
+    ),
+"#;
+
+fn render(template: &str, krate: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{crate}", krate)
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+        .replace("{dd}", &format!("{day:02}"))
+}
+
+fn invalid_data(path: &Path, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {message}", path.display()),
+    )
+}
+
+/// Declares the module `day` in `lib`, keeping the day modules sorted.
+fn declare_module(lib: &str, day: u8) -> Option<String> {
+    let declaration = format!("pub mod day{day:02};\n");
+    let mut offset = None;
+    for line in lib.split_inclusive('\n') {
+        let start = line.as_ptr() as usize - lib.as_ptr() as usize;
+        if line.starts_with("pub mod day") {
+            if *line >= *declaration {
+                offset = Some(start);
+                break;
+            }
+            offset = Some(start + line.len());
+        }
+    }
+    offset.map(|offset| [&lib[..offset], &declaration, &lib[offset..]].concat())
+}
+
+/// Appends `entry` to the `registry` constant of `runner`.
+fn register(runner: &str, registry: &str, entry: &str) -> Option<String> {
+    let start = runner.find(&format!("pub const {registry}"))?;
+    let end = start + runner[start..].find("\n];")? + 1;
+    Some([&runner[..end], entry, &runner[end..]].concat())
+}
+
+/// Generates the skeleton of a new puzzle day of `year` in the crate rooted at `root`,
+/// registers it with the runner and returns the created files.
+///
+/// The crate holds the puzzles of [`YEAR`] alone, so any other year is rejected.
+pub fn scaffold(root: &Path, krate: &str, year: u16, day: u8) -> io::Result<Vec<PathBuf>> {
+    if year != YEAR {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("this crate solves the puzzles of {YEAR}, not {year}"),
+        ));
+    }
+    let dd = format!("{day:02}");
+    let files = [
+        (
+            root.join(format!("src/day{dd}.rs")),
+            render(MODULE, krate, year, day),
+        ),
+        (
+            root.join(format!("src/bin/day{dd}.rs")),
+            render(BINARY, krate, year, day),
+        ),
+        (root.join(format!("data/day{dd}.txt")), String::new()),
+        (
+            root.join(format!("data/examples/day{dd}.txt")),
+            String::new(),
+        ),
+    ];
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        ));
+    }
+
+    let lib_path = root.join("src/lib.rs");
+    let lib = declare_module(&fs::read_to_string(&lib_path)?, day)
+        .ok_or_else(|| invalid_data(&lib_path, "no day modules are declared"))?;
+    let runner_path = root.join("src/runner.rs");
+    let runner = fs::read_to_string(&runner_path)?;
+    let runner = register(&runner, "PARSERS", &render(PARSER, krate, year, day))
+        .and_then(|runner| register(&runner, "SOLVERS", &render(SOLVER, krate, year, day)))
+        .ok_or_else(|| invalid_data(&runner_path, "no `PARSERS` and `SOLVERS` registries"))?;
+
+    for (path, contents) in &files {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+    }
+    fs::write(lib_path, lib)?;
+    fs::write(runner_path, runner)?;
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_declare_module() {
+        let lib = "pub mod day01;\npub mod day03;\n\nmod parse;\n";
+        assert_eq!(
+            declare_module(lib, 2).unwrap(),
+            "pub mod day01;\npub mod day02;\npub mod day03;\n\nmod parse;\n"
+        );
+        assert_eq!(
+            declare_module(lib, 4).unwrap(),
+            "pub mod day01;\npub mod day03;\npub mod day04;\n\nmod parse;\n"
+        );
+        assert_eq!(declare_module("mod parse;\n", 4), None);
+    }
+
+    #[test]
+    fn test_register() {
+        let runner = "pub const PARSERS: &[(u8, Parser)] = &[\n];\n\n\
+                      pub const SOLVERS: &[(u8, &[Solver])] = &[\n    (1, one),\n];\n";
+        assert_eq!(
+            register(runner, "SOLVERS", "    (2, two),\n").unwrap(),
+            "pub const PARSERS: &[(u8, Parser)] = &[\n];\n\n\
+             pub const SOLVERS: &[(u8, &[Solver])] = &[\n    (1, one),\n    (2, two),\n];\n"
+        );
+        assert_eq!(register(runner, "ANSWERS", "    (2, two),\n"), None);
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod day01;\n").unwrap();
+        fs::write(
+            root.join("src/runner.rs"),
+            "pub const PARSERS: &[(u8, Parser)] = &[\n];\npub const SOLVERS: &[(u8, &[Solver])] = &[\n];\n",
+        )
+        .unwrap();
+
+        let err = scaffold(&root, "aoc_2023", 2022, 2).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(!root.join("src/day02.rs").exists());
+        let files = scaffold(&root, "aoc_2023", 2023, 2).unwrap();
+        assert_eq!(files.len(), 4);
+        let module = fs::read_to_string(root.join("src/day02.rs")).unwrap();
+        assert!(module.starts_with("//! <https://adventofcode.com/2023/day/2>\n"));
+        assert_eq!(module.matches("#[ignore").count(), 2);
+        assert!(module.contains("include_str!(\"../data/examples/day02.txt\")"));
+        let binary = fs::read_to_string(root.join("src/bin/day02.rs")).unwrap();
+        assert!(binary.starts_with("use aoc_2023::day02::"));
+        assert_eq!(
+            fs::read_to_string(root.join("data/examples/day02.txt")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "pub mod day01;\npub mod day02;\n"
+        );
+        let runner = fs::read_to_string(root.join("src/runner.rs")).unwrap();
+        assert!(runner.contains("    (2, |input| crate::day02::parse_input(input).map(drop)),\n"));
+        assert!(runner.contains("        2,\n        &[\n            |input, _| {\n"));
+        assert!(runner.contains("answer!(solve_part2(&data))"));
+        let err = scaffold(&root, "aoc_2023", 2023, 2).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,304 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    budget::Budget,
+    runner::{self, Error},
+};
+
+/// Largest request body accepted, comfortably above the size of any puzzle input.
+const MAX_BODY: usize = 1 << 20;
+
+/// Largest request line and headers accepted, together.
+const MAX_HEAD: u64 = 8 << 10;
+
+/// How long a client may keep the server waiting for the next bytes of its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn error(status: u16, kind: &str, message: impl ToString) -> Self {
+        Self {
+            status,
+            body: json!({ "error": { "kind": kind, "message": message.to_string() } }),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Extracts the day and part from a `/day/<day>/part/<part>` path.
+fn route(path: &str) -> Option<(u8, usize)> {
+    match path.split('/').collect::<Vec<_>>()[..] {
+        ["", "day", day, "part", part] => Some((day.parse().ok()?, part.parse().ok()?)),
+        _ => None,
+    }
+}
+
+fn solve(day: u8, part: usize, input: &str, timeout: Duration) -> Response {
+    let Some(solvers) = runner::solvers(day) else {
+        return Response::error(404, "not_found", format!("day {day} is not solved"));
+    };
+    let Some(&solver) = solvers.get(part.wrapping_sub(1)) else {
+        return Response::error(404, "not_found", format!("day {day} has no part {part}"));
+    };
+    let start = Instant::now();
+    let answer = match runner::solve_within(solver, input, Budget::unlimited().with_time(timeout)) {
+        Ok(answer) => answer,
+        Err(Error::Parse(e)) => {
+            return Response {
+                status: 422,
+                body: json!({
+                    "error": { "kind": "parse", "message": e.to_string(), "offset": e.offset }
+                }),
+            }
+        }
+        Err(e @ (Error::NoSolution | Error::Calibration(_))) => {
+            return Response::error(422, "no_solution", e)
+        }
+        Err(Error::Timeout(e)) => {
+            return Response {
+                status: 503,
+                body: json!({
+                    "error": { "kind": "timeout", "message": e.to_string(), "steps": e.steps }
+                }),
+            }
+        }
+        Err(e @ Error::Panicked) => return Response::error(500, "panic", e),
+    };
+    let elapsed = start.elapsed();
+    Response {
+        status: 200,
+        body: json!({
+            "day": day,
+            "part": part,
+            "answer": Value::from(&answer),
+            "elapsed_ms": elapsed.as_secs_f64() * 1e3,
+        }),
+    }
+}
+
+fn respond(reader: &mut impl BufRead, timeout: Duration) -> io::Result<Response> {
+    let mut head = reader.take(MAX_HEAD);
+    let mut request_line = String::new();
+    head.read_line(&mut request_line)?;
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if head.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    if head.limit() == 0 {
+        return Ok(Response::error(
+            431,
+            "headers_too_large",
+            format!("the request line and headers are limited to {MAX_HEAD} bytes"),
+        ));
+    }
+
+    let (method, path) = match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+        [method, path, _version] => (method, path),
+        _ => {
+            return Ok(Response::error(
+                400,
+                "bad_request",
+                "malformed request line",
+            ))
+        }
+    };
+    let Some((day, part)) = route(path) else {
+        return Ok(Response::error(
+            404,
+            "not_found",
+            format!("no route for {path}, expected /day/<day>/part/<part>"),
+        ));
+    };
+    if method != "POST" {
+        return Ok(Response::error(
+            405,
+            "method_not_allowed",
+            "the puzzle input must be POSTed",
+        ));
+    }
+    let Some(content_length) = content_length else {
+        return Ok(Response::error(
+            400,
+            "bad_request",
+            "missing Content-Length",
+        ));
+    };
+    let Ok(length) = content_length.parse::<usize>() else {
+        return Ok(Response::error(
+            400,
+            "bad_request",
+            format!("invalid Content-Length {content_length:?}"),
+        ));
+    };
+    if length > MAX_BODY {
+        return Ok(Response::error(
+            413,
+            "payload_too_large",
+            format!("inputs are limited to {MAX_BODY} bytes"),
+        ));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(match String::from_utf8(body) {
+        Ok(input) => solve(day, part, &input, timeout),
+        Err(_) => Response::error(400, "bad_request", "the input is not valid UTF-8"),
+    })
+}
+
+fn handle(stream: TcpStream, timeout: Duration) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = respond(&mut BufReader::new(&stream), timeout)?;
+    let body = response.body.to_string();
+    write!(
+        &stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        response.reason(),
+        body.len(),
+    )?;
+    // Closing with part of the request unread would reset the connection, which can discard the
+    // response before the client reads it, so the rest of the request is read first.
+    stream.shutdown(Shutdown::Write)?;
+    io::copy(&mut (&stream).take(MAX_BODY as u64), &mut io::sink()).map(drop)
+}
+
+/// Answers requests of the form `POST /day/<day>/part/<part>`, whose body is the puzzle input,
+/// with the answer and the time taken to solve it, each connection on its own thread.
+///
+/// Solvers that support it give up after `timeout`. A connection that cannot be accepted is
+/// reported and skipped.
+pub fn serve(listener: TcpListener, timeout: Duration) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("accept: {e}");
+                continue;
+            }
+        };
+        thread::spawn(move || handle(stream, timeout));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn request(raw: &str) -> (u16, Value) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, Duration::from_secs(10)));
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn post(path: &str, input: &str) -> (u16, Value) {
+        request(&format!(
+            "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{input}",
+            input.len()
+        ))
+    }
+
+    #[test]
+    fn test_answer() {
+        let (status, body) = post("/day/15/part/1", "rn=1,cm-\n");
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], 283);
+        assert_eq!(body["day"], 15);
+        assert!(body["elapsed_ms"].is_f64());
+        let (status, body) = post("/day/15/part/2", "rn=1,cm-\n");
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], 1);
+        let network = "broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a\n";
+        let (status, body) = post("/day/20/part/1", network);
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], 32_000_000);
+        let ghosts = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n\
+                      22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n\
+                      22Z = (22B, 22B)\nXXX = (XXX, XXX)\n";
+        let (status, body) = post("/day/8/part/2", ghosts);
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], 6);
+        let (status, _) = post("/day/8/part/1", ghosts);
+        assert_eq!(status, 422);
+    }
+
+    #[test]
+    fn test_errors() {
+        let (status, body) = post("/day/15/part/1", "rn=1,HASH\n");
+        assert_eq!(status, 422);
+        assert_eq!(body["error"]["kind"], "parse");
+        assert_eq!(body["error"]["offset"], 5);
+        let (status, body) = post("/day/1/part/1", "1\nx\n");
+        assert_eq!(status, 422);
+        assert_eq!(
+            body["error"]["message"],
+            "the input has no solution: line 2: no digit"
+        );
+        let (status, body) = post("/day/25/part/2", "a: b\n");
+        assert_eq!(status, 404);
+        assert_eq!(body["error"]["message"], "day 25 has no part 2");
+        let (status, body) = post("/day/15/part/3", "rn=1\n");
+        assert_eq!(status, 404);
+        assert_eq!(body["error"]["kind"], "not_found");
+        let (status, _) = post("/day/15", "rn=1\n");
+        assert_eq!(status, 404);
+        let (status, body) = request("GET /day/15/part/1 HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 405);
+        assert_eq!(body["error"]["kind"], "method_not_allowed");
+        let (status, body) = request(&format!(
+            "POST /day/15/part/1 HTTP/1.1\r\nCookie: {}\r\nContent-Length: 5\r\n\r\nrn=1\n",
+            "x".repeat(MAX_HEAD as usize)
+        ));
+        assert_eq!(status, 431);
+        assert_eq!(body["error"]["kind"], "headers_too_large");
+        let (status, body) =
+            request("POST /day/15/part/1 HTTP/1.1\r\nContent-Length: five\r\n\r\nrn=1\n");
+        assert_eq!(status, 400);
+        assert_eq!(body["error"]["message"], "invalid Content-Length \"five\"");
+        let (status, body) = request("POST /day/15/part/1 HTTP/1.1\r\n\r\nrn=1\n");
+        assert_eq!(status, 400);
+        assert_eq!(body["error"]["message"], "missing Content-Length");
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+use crate::{day03, day08, day10, day17, day19, day20, day21, day22, day23, day25, ParseError};
+
+/// A named measurement of the shape of a puzzle input.
+pub type Stat = (&'static str, String);
+
+#[derive(Debug)]
+pub enum Error {
+    Unsupported(u8),
+    Parse(ParseError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unsupported(day) => write!(f, "day {day} has no statistics"),
+            Error::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// Parses `input` as the puzzle input of `day` and measures its structure.
+pub fn stats(day: u8, input: &str) -> Result<Vec<Stat>, Error> {
+    Ok(match day {
+        3 => day03::stats(input)?,
+        8 => day08::stats(input)?,
+        10 => day10::stats(input)?,
+        17 => day17::stats(input)?,
+        19 => day19::stats(input)?,
+        20 => day20::stats(input)?,
+        21 => day21::stats(input)?,
+        22 => day22::stats(input)?,
+        23 => day23::stats(input)?,
+        25 => day25::stats(input)?,
+        _ => return Err(Error::Unsupported(day)),
+    })
+}
@@ -0,0 +1,164 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use inotify::{Inotify, WatchMask};
+
+use crate::{
+    budget::Budget,
+    runner::{self, Solver},
+    Answer,
+};
+
+/// Answers to the parts of a puzzle, or why each could not be computed.
+pub type Answers = Vec<Result<Answer, String>>;
+
+/// Solves `input` with each of `solvers`, each within a renewal of `budget`, turning parse
+/// errors, timeouts and solver panics into messages.
+pub fn solve(solvers: &[Solver], input: &str, budget: &Budget) -> Answers {
+    solvers
+        .iter()
+        .map(|&solver| {
+            runner::solve_within(solver, input, budget.renewed()).map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+/// Describes the answers in `current`, pointing out those that differ from `previous`.
+pub fn diff(previous: Option<&Answers>, current: &Answers) -> Vec<String> {
+    current
+        .iter()
+        .enumerate()
+        .map(
+            |(i, answer)| match (answer, previous.and_then(|p| p.get(i))) {
+                (Err(e), _) => format!("part {}: error: {e}", i + 1),
+                (Ok(answer), Some(Ok(before))) if before != answer => {
+                    format!("part {}: {before} -> {answer}", i + 1)
+                }
+                (Ok(answer), _) => format!("part {}: {answer}", i + 1),
+            },
+        )
+        .collect()
+}
+
+fn report(
+    solvers: &[Solver],
+    budget: &Budget,
+    path: &Path,
+    answers: &mut HashMap<PathBuf, Answers>,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    writeln!(output, "{}", path.display())?;
+    let current = match fs::read_to_string(path) {
+        Ok(input) => solve(solvers, &input, budget),
+        Err(e) => {
+            answers.remove(path);
+            return writeln!(output, "  error: {e}");
+        }
+    };
+    for line in diff(answers.get(path), &current) {
+        writeln!(output, "  {line}")?;
+    }
+    answers.insert(path.to_path_buf(), current);
+    Ok(())
+}
+
+/// Solves each of `files` with `solvers`, then solves them again whenever they are written to,
+/// until an error occurs. Each part is given `budget` anew every time it is solved.
+///
+/// The parent directories are watched rather than the files themselves, so that editors
+/// replacing a file on save are noticed as well.
+pub fn watch(
+    solvers: &[Solver],
+    budget: &Budget,
+    files: &[PathBuf],
+    mut output: impl Write,
+) -> io::Result<()> {
+    let mut inotify = Inotify::init()?;
+    let mut directories = HashMap::new();
+    for file in files {
+        let directory = match file.parent() {
+            Some(parent) if parent != Path::new("") => parent,
+            _ => Path::new("."),
+        };
+        let descriptor = inotify
+            .watches()
+            .add(directory, WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO)?;
+        directories.insert(descriptor, file.parent().unwrap_or(Path::new("")));
+    }
+
+    let mut answers = HashMap::new();
+    for file in files {
+        report(solvers, budget, file, &mut answers, &mut output)?;
+    }
+    let mut buffer = [0; 4096];
+    loop {
+        let mut changed = HashSet::new();
+        for event in inotify.read_events_blocking(&mut buffer)? {
+            if let (Some(directory), Some(name)) = (directories.get(&event.wd), event.name) {
+                let path = directory.join(name);
+                if files.contains(&path) {
+                    changed.insert(path);
+                }
+            }
+        }
+        for file in files.iter().filter(|file| changed.contains(*file)) {
+            report(solvers, budget, file, &mut answers, &mut output)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let before: Answers = vec![Ok(Answer::from(35u64)), Ok(Answer::from(46u64))];
+        let after: Answers = vec![Ok(Answer::from(36u64)), Ok(Answer::from(46u64))];
+        assert_eq!(diff(None, &before), ["part 1: 35", "part 2: 46"]);
+        assert_eq!(
+            diff(Some(&before), &after),
+            ["part 1: 35 -> 36", "part 2: 46"]
+        );
+        let failed: Answers = vec![
+            Err("the solver panicked".to_string()),
+            Ok(Answer::from(46u64)),
+        ];
+        assert_eq!(
+            diff(Some(&before), &failed),
+            ["part 1: error: the solver panicked", "part 2: 46"]
+        );
+        assert_eq!(diff(Some(&failed), &after), ["part 1: 36", "part 2: 46"]);
+    }
+
+    #[test]
+    fn test_solve() {
+        let solvers = crate::runner::solvers(15).unwrap();
+        let budget = Budget::unlimited();
+        assert_eq!(
+            solve(solvers, "HASH\n", &budget),
+            [
+                Err("invalid input at byte 0".to_string()),
+                Err("invalid input at byte 0".to_string())
+            ]
+        );
+        assert_eq!(
+            solve(solvers, "rn=1,cm-\n", &budget),
+            [Ok(Answer::from(283u64)), Ok(Answer::from(1u64))]
+        );
+        let solvers = crate::runner::solvers(8).unwrap();
+        let budget = Budget::unlimited().with_steps(10);
+        let answers = solve(
+            solvers,
+            "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n",
+            &budget,
+        );
+        assert!(answers.iter().all(|answer| answer
+            .as_ref()
+            .is_err_and(|e| e.starts_with("gave up after 10 steps"))));
+    }
+}